@@ -1,13 +1,18 @@
-use tokio;
+use std::sync::Arc;
+
 use zotero_api::{Zotero, ZoteroApi, ZoteroApiAsyncExecutor, ZoteroApiError};
 use zotero_data::item::Item;
 
 #[tokio::main]
 async fn main() {
-    let zotero_api = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
-    let result: Result<Vec<Item>, ZoteroApiError> =
-        zotero_api.get_items(None).execute(&zotero_api).await;
-    match result {
+    // the client owns its credentials, so it can be shared between tasks
+    let zotero_api = Arc::new(Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ"));
+    let task = tokio::spawn(async move {
+        let result: Result<Vec<Item>, ZoteroApiError> =
+            zotero_api.get_items(None).execute(&*zotero_api).await;
+        result
+    });
+    match task.await.unwrap() {
         Ok(items) => println!("{}", items.len()),
         Err(err) => println!("{err:?}"),
     };
@@ -1,12 +1,22 @@
 use bytes::Bytes;
-use http::{header::HeaderValue, header::AUTHORIZATION, Request};
+use http::{header::AUTHORIZATION, Request};
 use serde::Serialize;
-use url::form_urlencoded::byte_serialize;
+use std::collections::HashSet;
+use zotero_data::item::Item;
 
-pub trait ZoteroApi<'a> {
+use crate::batch::{self, BatchResult, KeyBatch};
+use crate::consts::{ZOTERO_BASE_URL, ZOTERO_WRITE_LIMIT};
+use crate::transport::{AsyncHttpTransport, HttpTransport};
+use crate::{ZoteroApiError, ZoteroApiExecutor};
+
+pub trait ZoteroApi {
     fn get_base_url(&self) -> String;
-    fn get_id(&self) -> &'a str;
-    fn get_api_key(&self) -> Option<&'a str>;
+    fn get_id(&self) -> &str;
+    fn get_api_key(&self) -> Option<&str>;
+    /// The client sending requests executed with [`ZoteroApiExecutor`](crate::ZoteroApiExecutor).
+    fn transport(&self) -> &dyn HttpTransport;
+    /// The client sending requests executed with [`ZoteroApiAsyncExecutor`](crate::ZoteroApiAsyncExecutor).
+    fn async_transport(&self) -> &dyn AsyncHttpTransport;
 
     fn request_uri(&self, method: &str, uri: String) -> Request<Bytes> {
         let mut builder = Request::builder().method(method).uri(uri);
@@ -16,7 +26,7 @@ pub trait ZoteroApi<'a> {
         builder.body(Bytes::new()).unwrap()
     }
 
-    fn request<I: Into<Option<&'a str>>, T: Serialize>(
+    fn request<'p, I: Into<Option<&'p str>>, T: Serialize>(
         &self,
         method: &str,
         params: String,
@@ -45,13 +55,19 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let key_info_request = z.get_api_key_info("bZARysJ579K5SdmYuaAJ");
     /// ```
-    fn get_api_key_info<I: Into<Option<&'a str>>>(
+    fn get_api_key_info<'p, I: Into<Option<&'p str>>>(
         &self,
         extra_params: I,
     ) -> Option<Request<Bytes>> {
         if let Some(api_key) = self.get_api_key() {
-            let params = format!("/keys/{}", api_key);
-            Some(self.request::<_, ()>("GET", params, extra_params, None))
+            // keys are not scoped to a library
+            let uri = match extra_params.into() {
+                None => format!("{}keys/{}", ZOTERO_BASE_URL, api_key),
+                Some(extra_params) => {
+                    format!("{}keys/{}?{}", ZOTERO_BASE_URL, api_key, extra_params)
+                }
+            };
+            Some(self.request_uri("GET", uri))
         } else {
             None
         }
@@ -63,9 +79,9 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let item_request = z.get_item("B8ZNE3GH", None);
     /// ```
-    fn get_item<I: Into<Option<&'a str>>>(
+    fn get_item<'p, S: AsRef<str> + std::fmt::Display, I: Into<Option<&'p str>>>(
         &self,
-        item_id: &'a str,
+        item_id: S,
         extra_params: I,
     ) -> Request<Bytes> {
         let params: String = format!("/items/{}", item_id);
@@ -78,33 +94,90 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let items_request = z.get_items(None);
     /// ```
-    fn get_items<I: Into<Option<&'a str>>>(&self, extra_params: I) -> Request<Bytes> {
+    fn get_items<'p, I: Into<Option<&'p str>>>(&self, extra_params: I) -> Request<Bytes> {
         let params = "/items".to_string();
         self.request::<_, ()>("GET", params, extra_params, None)
     }
 
+    /// Generate Api requests to retrieve items by key, one request per batch of 50 items.
+    /// Run them with [`ZoteroApi::execute_batches`] to get all the items at once.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let items_batches = z.get_items_by_keys(&["PJTUB2WE", "YXT5PJU9"], None);
+    /// ```
+    fn get_items_by_keys<'p, S: AsRef<str>, I: Into<Option<&'p str>>>(
+        &self,
+        items_keys: &[S],
+        extra_params: I,
+    ) -> Vec<KeyBatch> {
+        let extra_params = match extra_params.into() {
+            Some(extra_params) => format!("limit={}&{}", ZOTERO_WRITE_LIMIT, extra_params),
+            None => format!("limit={}", ZOTERO_WRITE_LIMIT),
+        };
+        batch::key_batches(
+            self,
+            "GET",
+            "/items",
+            "itemKey",
+            items_keys,
+            Some(&extra_params),
+            None,
+        )
+    }
+
     /// Generate Api request to retreive all child items of a specific item
     /// ```no_run
     /// # use zotero_api::{Zotero, ZoteroApi};
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let items_request = z.get_child_items("B8ZNE3GH", None);
     /// ```
-    fn get_child_items<I: Into<Option<&'a str>>>(
+    fn get_child_items<'p, S: AsRef<str> + std::fmt::Display, I: Into<Option<&'p str>>>(
         &self,
-        item_id: &'a str,
+        item_id: S,
         extra_params: I,
     ) -> Request<Bytes> {
         let params: String = format!("/items/{}/children", item_id);
         self.request::<_, ()>("GET", params, extra_params, None)
     }
 
+    /// Generate Api request to retreive the annotations of an attachment.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiExecutor};
+    /// # use zotero_data::item::{annotations_to_markdown, AnnotationData, Item, ItemType};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let items: Vec<Item> = z.get_annotations("QGKIJ5HT", None).execute(&z).unwrap();
+    /// let annotations: Vec<AnnotationData> = items
+    ///     .into_iter()
+    ///     .filter_map(|item| match item.data {
+    ///         ItemType::Annotation(annotation) => Some(annotation),
+    ///         _ => None,
+    ///     })
+    ///     .collect();
+    /// println!("{}", annotations_to_markdown(&annotations));
+    /// ```
+    fn get_annotations<'p, S: AsRef<str> + std::fmt::Display, I: Into<Option<&'p str>>>(
+        &self,
+        attachment_id: S,
+        extra_params: I,
+    ) -> Request<Bytes> {
+        let params = match extra_params.into() {
+            Some(extra_params) => format!(
+                "/items/{}/children?itemType=annotation&{}",
+                attachment_id, extra_params
+            ),
+            None => format!("/items/{}/children?itemType=annotation", attachment_id),
+        };
+        self.request::<_, ()>("GET", params, None, None)
+    }
+
     /// Generate Api request to retreive top-level items in the library, excluding trashed items.
     /// ```no_run
     /// # use zotero_api::{Zotero, ZoteroApi};
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let top_items_request = z.get_top_items(None);
     /// ```
-    fn get_top_items<I: Into<Option<&'a str>>>(&self, extra_params: I) -> Request<Bytes> {
+    fn get_top_items<'p, I: Into<Option<&'p str>>>(&self, extra_params: I) -> Request<Bytes> {
         let params = "/items/top".to_string();
         self.request::<_, ()>("GET", params, extra_params, None)
     }
@@ -115,7 +188,7 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let trashed_items_request = z.get_trashed_items(None);
     /// ```
-    fn get_trashed_items<I: Into<Option<&'a str>>>(&self, extra_params: I) -> Request<Bytes> {
+    fn get_trashed_items<'p, I: Into<Option<&'p str>>>(&self, extra_params: I) -> Request<Bytes> {
         let params = "/items/trash".to_string();
         self.request::<_, ()>("GET", params, extra_params, None)
     }
@@ -126,7 +199,7 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let my_publications_request = z.get_publications(None);
     /// ```
-    fn get_publications<I: Into<Option<&'a str>>>(&self, extra_params: I) -> Request<Bytes> {
+    fn get_publications<'p, I: Into<Option<&'p str>>>(&self, extra_params: I) -> Request<Bytes> {
         let params = "/publications/items".to_string();
         self.request::<_, ()>("GET", params, extra_params, None)
     }
@@ -137,9 +210,9 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let collection_request = z.get_collection("AYVWED", None);
     /// ```
-    fn get_collection<I: Into<Option<&'a str>>>(
+    fn get_collection<'p, S: AsRef<str> + std::fmt::Display, I: Into<Option<&'p str>>>(
         &self,
-        collection_id: &'a str,
+        collection_id: S,
         extra_params: I,
     ) -> Request<Bytes> {
         let params = format!("/collections/{}", collection_id);
@@ -152,7 +225,7 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let collections_request = z.get_collections(None);
     /// ```
-    fn get_collections<I: Into<Option<&'a str>>>(&self, extra_params: I) -> Request<Bytes> {
+    fn get_collections<'p, I: Into<Option<&'p str>>>(&self, extra_params: I) -> Request<Bytes> {
         let params = "/collections".to_string();
         self.request::<_, ()>("GET", params, extra_params, None)
     }
@@ -163,7 +236,7 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let top_collections_request = z.get_top_collections(None);
     /// ```
-    fn get_top_collections<I: Into<Option<&'a str>>>(&self, extra_params: I) -> Request<Bytes> {
+    fn get_top_collections<'p, I: Into<Option<&'p str>>>(&self, extra_params: I) -> Request<Bytes> {
         let params = "/collections/top".to_string();
         self.request::<_, ()>("GET", params, extra_params, None)
     }
@@ -174,9 +247,9 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let collection_items_request = z.get_collection_items("AYVWED", None);
     /// ```
-    fn get_collection_items<I: Into<Option<&'a str>>>(
+    fn get_collection_items<'p, S: AsRef<str> + std::fmt::Display, I: Into<Option<&'p str>>>(
         &self,
-        collection_id: &'a str,
+        collection_id: S,
         extra_params: I,
     ) -> Request<Bytes> {
         let params = format!("/collections/{}/items", collection_id);
@@ -189,191 +262,135 @@ pub trait ZoteroApi<'a> {
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// let collection_top_items_request = z.get_collection_top_items("AYVWED", "B8ZNE3GH", None);
     /// ```
-    fn get_collection_top_items<I: Into<Option<&'a str>>>(
+    fn get_collection_top_items<'p, S: AsRef<str> + std::fmt::Display, I: Into<Option<&'p str>>>(
         &self,
-        collection_id: &'a str,
-        item_id: &'a str,
+        collection_id: S,
+        item_id: S,
         extra_params: I,
     ) -> Request<Bytes> {
         let params = format!("/collection/{}/items/{}", collection_id, item_id);
         self.request::<_, ()>("GET", params, extra_params, None)
     }
 
-    /// Generate Api request to delete a Zotero item.
-    /// ```no_run
-    /// # use zotero_api::{Zotero, ZoteroApi};
-    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
-    /// let delete_req = z.delete_item("PJTUB2WE", "2050");
-    /// ```
-    fn delete_item<S: AsRef<str> + std::fmt::Display>(
-        &self,
-        item_key: S,
-        last_version: S,
-    ) -> Request<Bytes> {
-        let params = format!("items/{}", item_key);
-        let mut req = self.request::<_, ()>("DELETE", params, None, None);
-        req.headers_mut().insert(
-            "If-Unmodified-Since-Version",
-            HeaderValue::from_str(last_version.as_ref()).unwrap(),
-        );
-        req
-    }
-
-    /// Generate Api request to delete a Zotero item.
+    /// Execute batches of requests in order, merging their results. A failed batch is reported in the
+    /// `failures` of the result and doesn't stop the following batches.
     /// ```no_run
     /// # use zotero_api::{Zotero, ZoteroApi};
+    /// # use zotero_data::item::Item;
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
-    /// let delete_req = z.delete_items(vec!["PJTUB2WE", "YXT5PJU9"], "2050");
+    /// let result = z.execute_batches::<Item>(z.get_items_by_keys(&["PJTUB2WE", "YXT5PJU9"], None));
+    /// for key in result.failed_keys() {
+    ///     eprintln!("{} not retrieved", key);
+    /// }
     /// ```
-    fn delete_items<S: AsRef<str> + std::fmt::Display>(
+    fn execute_batches<T: serde::de::DeserializeOwned>(
         &self,
-        items_keys: Vec<S>,
-        last_version: S,
-    ) -> Request<Bytes> {
-        let params = format!(
-            "items/{}",
-            items_keys
-                .iter()
-                .map(|elem| elem.as_ref())
-                .collect::<Vec<&str>>()
-                .join(" || ")
-        );
-        let mut req = self.request::<_, ()>("DELETE", params, None, None);
-        req.headers_mut().insert(
-            "If-Unmodified-Since-Version",
-            HeaderValue::from_str(last_version.as_ref()).unwrap(),
-        );
-        req
+        batches: Vec<KeyBatch>,
+    ) -> BatchResult<T>
+    where
+        Self: Sized,
+    {
+        batch::execute(self.transport(), batches)
     }
 
-    /// Generate Api request to delete a Zotero collection.
+    /// Fetch the top-level items with their notes and attachments, and the annotations of the attachments.
+    /// `extra_params` apply to the top-level items, e.g. `tag=history`.
     /// ```no_run
     /// # use zotero_api::{Zotero, ZoteroApi};
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
-    /// let delete_req = z.delete_collection("TYQDGEZR", "2050");
+    /// let tree = z.get_item_tree(None).unwrap();
+    /// for root in &tree.roots {
+    ///     println!("{}: {} attachment(s)", root.item.title(), root.attachments().count());
+    /// }
     /// ```
-    fn delete_collection<S: AsRef<str> + std::fmt::Display>(
+    fn get_item_tree<'p, I: Into<Option<&'p str>>>(
         &self,
-        item_key: S,
-        last_version: S,
-    ) -> Request<Bytes> {
-        let params = format!("collections/{}", item_key);
-        let mut req = self.request::<_, ()>("DELETE", params, None, None);
-        req.headers_mut().insert(
-            "If-Unmodified-Since-Version",
-            HeaderValue::from_str(last_version.as_ref()).unwrap(),
-        );
-        req
-    }
+        extra_params: I,
+    ) -> Result<zotero_data::item::ItemTree, ZoteroApiError>
+    where
+        Self: Sized,
+    {
+        use zotero_data::item::{ItemTree, ItemType};
 
-    /// Generate Api request to delete a Zotero search.
-    fn delete_search<S: AsRef<str> + std::fmt::Display>(
-        &self,
-        search_key: S,
-        last_version: S,
-    ) -> Request<Bytes> {
-        let params = format!("searches?searchKey={}", search_key);
-        let mut req = self.request::<_, ()>("DELETE", params, None, None);
-        req.headers_mut().insert(
-            "If-Unmodified-Since-Version",
-            HeaderValue::from_str(last_version.as_ref()).unwrap(),
-        );
-        req
+        let mut items: Vec<Item> = self.get_top_items(extra_params).execute(self)?;
+        let mut parents: Vec<String> = items
+            .iter()
+            .filter(|item| item.meta.has_children())
+            .map(|item| item.key.clone())
+            .collect();
+        while let Some(parent) = parents.pop() {
+            let params = format!("/items/{}/children", parent);
+            let children: Vec<Item> = self
+                .request::<_, ()>("GET", params, None, None)
+                .execute(self)?;
+            // annotations are the children of attachments, which don't always tell how many they have
+            parents.extend(
+                children
+                    .iter()
+                    .filter(|child| {
+                        matches!(child.data, ItemType::Attachment(_))
+                            && (child.meta.num_children.is_none() || child.meta.has_children())
+                    })
+                    .map(|child| child.key.clone()),
+            );
+            items.extend(children);
+        }
+        Ok(ItemTree::from_items(items))
     }
 
-    /// Generate Api request to delete a Zotero tag.
+    /// Fetch every collection and arrange them by parent.
     /// ```no_run
     /// # use zotero_api::{Zotero, ZoteroApi};
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
-    /// let delete_req = z.delete_tag("history", "2050");
+    /// let tree = z.get_collection_tree(None).unwrap();
+    /// let sources = tree.get_by_path("Thesis/Chapter 2/Sources").unwrap();
+    /// println!("{}: {} item(s)", sources.key(), sources.total_items());
     /// ```
-    fn delete_tag<S: AsRef<str> + std::fmt::Display>(
+    fn get_collection_tree<'p, I: Into<Option<&'p str>>>(
         &self,
-        tag_key: S,
-        last_version: S,
-    ) -> Request<Bytes> {
-        let params = format!("tags?tag={}", tag_key);
-        let mut req = self.request::<_, ()>("DELETE", params, None, None);
-        req.headers_mut().insert(
-            "If-Unmodified-Since-Version",
-            HeaderValue::from_str(last_version.as_ref()).unwrap(),
-        );
-        req
+        extra_params: I,
+    ) -> Result<zotero_data::collection::CollectionTree, ZoteroApiError>
+    where
+        Self: Sized,
+    {
+        use zotero_data::collection::{Collection, CollectionTree};
+
+        let collections: Vec<Collection> = self.get_collections(extra_params).execute(self)?;
+        CollectionTree::from_collections(collections)
+            .map_err(|err| ZoteroApiError::ParseResponseError(err.to_string()))
     }
 
-    /// Generate Api request to delete multiple Zotero tags.
+    /// Fetch the items of a collection and of all its subcollections. Items belonging to several of these
+    /// collections are returned once.
     /// ```no_run
     /// # use zotero_api::{Zotero, ZoteroApi};
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
-    /// let delete_req = z.delete_tags(vec!["history", "philosophy", "art"], "2050");
+    /// let tree = z.get_collection_tree(None).unwrap();
+    /// let thesis = tree.get_by_path("Thesis").unwrap();
+    /// let items = z.get_collection_items_recursive(thesis, None).unwrap();
     /// ```
-    fn delete_tags<S: AsRef<str> + std::fmt::Display>(
-        &self,
-        tags_keys: Vec<S>,
-        last_version: S,
-    ) -> Request<Bytes> {
-        let params = format!(
-            "tags?tag={}",
-            tags_keys
-                .iter()
-                .map(|elem| byte_serialize(elem.as_ref().as_bytes()).collect())
-                .collect::<Vec<String>>()
-                .join(" || ")
-        );
-        let mut req = self.request::<_, ()>("DELETE", params, None, None);
-        req.headers_mut().insert(
-            "If-Unmodified-Since-Version",
-            HeaderValue::from_str(last_version.as_ref()).unwrap(),
-        );
-        req
-    }
-
-    /// Generate Api request to create an item
-    fn create_new_item<T: Serialize>(&self, item: T) -> Request<Bytes> {
-        let params = "/items".to_string();
-        self.request("POST", params, None, Some(&vec![&item]))
-    }
-
-    /// Generate Api request to create multiple items
-    fn create_new_items<T: Serialize>(&self, item: Vec<T>) -> Request<Bytes> {
-        let params = "/items".to_string();
-        self.request("POST", params, None, Some(&item))
-    }
-
-    /// Generate Api request to create new collection
-    fn create_new_collection<T: Serialize>(&self, item: T) -> Request<Bytes> {
-        let params = "/collections".to_string();
-        self.request("POST", params, None, Some(&vec![&item]))
-    }
-
-    /// Generate Api request to create new collections
-    fn create_new_collections<T: Serialize>(&self, item: Vec<T>) -> Request<Bytes> {
-        let params = "/collections".to_string();
-        self.request("POST", params, None, Some(&item))
-    }
-
-    /// Generate Api request to update a zotero item.
-    /// ```no_run
-    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiExecutor, ZoteroApiError};
-    /// # use zotero_data::item::ItemType;
-    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
-    /// let remote_item: Result<ItemType, ZoteroApiError> = z.get_item("B8ZNE3GH", None).execute(&z);
-    ///
-    /// if let Ok(mut result) = remote_item {
-    ///     if let ItemType::Book(bookdata) = &mut result {
-    ///         bookdata.title = "A new title".to_string();
-    ///         bookdata.publisher = "Doe editions".to_string();
-    ///         bookdata.creators = vec!();
-    ///         let _: Result<(), _> = z.update_item(&bookdata.key, &bookdata).execute(&z);
-    ///     };
-    /// };
-    ///```
-    fn update_item<T: Serialize, S: AsRef<str> + std::fmt::Display>(
+    fn get_collection_items_recursive<'p, I: Into<Option<&'p str>>>(
         &self,
-        item_key: S,
-        item_data: T,
-    ) -> Request<Bytes> {
-        let params = format!("/items/{}", item_key);
-        self.request("PATCH", params, None, Some(&item_data))
+        collection: &zotero_data::collection::CollectionNode,
+        extra_params: I,
+    ) -> Result<Vec<Item>, ZoteroApiError>
+    where
+        Self: Sized,
+    {
+        let extra_params: Option<&'p str> = extra_params.into();
+        let mut items: Vec<Item> = Vec::new();
+        let mut seen: HashSet<String> = HashSet::new();
+        for key in collection.keys() {
+            let params = format!("/collections/{}/items", key);
+            let collection_items: Vec<Item> = self
+                .request::<_, ()>("GET", params, extra_params, None)
+                .execute(self)?;
+            for item in collection_items {
+                if seen.insert(item.key.clone()) {
+                    items.push(item);
+                }
+            }
+        }
+        Ok(items)
     }
 }
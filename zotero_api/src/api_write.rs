@@ -0,0 +1,615 @@
+use bytes::Bytes;
+use http::{header::HeaderValue, Request};
+use serde::Serialize;
+use url::form_urlencoded::byte_serialize;
+use zotero_data::collection::Collection;
+use zotero_data::item::Item;
+use zotero_data::relations::RelationPredicate;
+use zotero_data::shared_fields::ItemFields;
+
+use crate::batch::{self, BatchResult, KeyBatch};
+use crate::consts::{ZOTERO_BASE_URL, ZOTERO_WRITE_LIMIT};
+use crate::executor::execute_versioned;
+use crate::merge::{self, MergePlan};
+use crate::{ZoteroApi, ZoteroApiError, ZoteroApiExecutor};
+
+/// Requests modifying a library. Only clients with write access implement this trait, such as
+/// `Zotero<ReadWrite>`: a read-only client can't build a write request by mistake.
+pub trait ZoteroApiWrite: ZoteroApi {
+    /// Generate Api request to delete a Zotero item.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let delete_req = z.delete_item("PJTUB2WE", "2050");
+    /// ```
+    fn delete_item<S: AsRef<str> + std::fmt::Display>(
+        &self,
+        item_key: S,
+        last_version: S,
+    ) -> Request<Bytes> {
+        let params = format!("/items/{}", item_key);
+        let mut req = self.request::<_, ()>("DELETE", params, None, None);
+        req.headers_mut().insert(
+            "If-Unmodified-Since-Version",
+            HeaderValue::from_str(last_version.as_ref()).unwrap(),
+        );
+        req
+    }
+
+    /// Generate Api requests to delete Zotero items, one request per batch of 50 items.
+    /// Run them with [`ZoteroApi::execute_batches`], which keeps the library version up to date between batches.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let delete_batches = z.delete_items(&["PJTUB2WE", "YXT5PJU9"], "2050");
+    /// ```
+    fn delete_items<S: AsRef<str>>(&self, items_keys: &[S], last_version: &str) -> Vec<KeyBatch> {
+        batch::key_batches(
+            self,
+            "DELETE",
+            "/items",
+            "itemKey",
+            items_keys,
+            None,
+            Some(last_version),
+        )
+    }
+
+    /// Generate Api request to move a Zotero item to the trash. Unlike [`ZoteroApiWrite::delete_item`], the item can
+    /// be restored.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let trash_req = z.trash_item("PJTUB2WE", "2050");
+    /// ```
+    fn trash_item<S: AsRef<str> + std::fmt::Display>(
+        &self,
+        item_key: S,
+        last_version: S,
+    ) -> Request<Bytes> {
+        trash_request(self, item_key, last_version, true)
+    }
+
+    /// Generate Api request to restore a Zotero item from the trash.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let restore_req = z.restore_item("PJTUB2WE", "2050");
+    /// ```
+    fn restore_item<S: AsRef<str> + std::fmt::Display>(
+        &self,
+        item_key: S,
+        last_version: S,
+    ) -> Request<Bytes> {
+        trash_request(self, item_key, last_version, false)
+    }
+
+    /// Permanently delete every item in the trash, by batches of 50. Each batch fails if the library was
+    /// modified by someone else in the meantime. The keys of the deleted items are in `succeeded`.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let result = z.empty_trash().unwrap();
+    /// println!("{} item(s) deleted", result.succeeded.len());
+    /// ```
+    fn empty_trash(&self) -> Result<BatchResult, ZoteroApiError>
+    where
+        Self: Sized,
+    {
+        // `format=versions` lists every trashed item at once, along with the library version
+        let request =
+            self.request::<_, ()>("GET", "/items/trash".to_string(), "format=versions", None);
+        let (versions, library_version) = execute_versioned(self.transport(), request)?;
+        let mut keys: Vec<String> = versions
+            .as_object()
+            .map(|versions| versions.keys().cloned().collect())
+            .unwrap_or_default();
+        keys.sort();
+
+        Ok(batch::execute(
+            self.transport(),
+            self.delete_items(&keys, &library_version.to_string()),
+        ))
+    }
+
+    /// Generate Api request to delete a Zotero collection.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let delete_req = z.delete_collection("TYQDGEZR", "2050");
+    /// ```
+    fn delete_collection<S: AsRef<str> + std::fmt::Display>(
+        &self,
+        item_key: S,
+        last_version: S,
+    ) -> Request<Bytes> {
+        let params = format!("/collections/{}", item_key);
+        let mut req = self.request::<_, ()>("DELETE", params, None, None);
+        req.headers_mut().insert(
+            "If-Unmodified-Since-Version",
+            HeaderValue::from_str(last_version.as_ref()).unwrap(),
+        );
+        req
+    }
+
+    /// Generate Api requests to delete Zotero collections, one request per batch of 50 collections.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let delete_batches = z.delete_collections(&["TYQDGEZR", "AYVWED"], "2050");
+    /// ```
+    fn delete_collections<S: AsRef<str>>(
+        &self,
+        collections_keys: &[S],
+        last_version: &str,
+    ) -> Vec<KeyBatch> {
+        batch::key_batches(
+            self,
+            "DELETE",
+            "/collections",
+            "collectionKey",
+            collections_keys,
+            None,
+            Some(last_version),
+        )
+    }
+
+    /// Generate Api request to delete a Zotero search.
+    fn delete_search<S: AsRef<str> + std::fmt::Display>(
+        &self,
+        search_key: S,
+        last_version: S,
+    ) -> Request<Bytes> {
+        let params = format!("/searches?searchKey={}", search_key);
+        let mut req = self.request::<_, ()>("DELETE", params, None, None);
+        req.headers_mut().insert(
+            "If-Unmodified-Since-Version",
+            HeaderValue::from_str(last_version.as_ref()).unwrap(),
+        );
+        req
+    }
+
+    /// Generate Api requests to delete Zotero searches, one request per batch of 50 searches.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let delete_batches = z.delete_searches(&["HHF7BB4C", "DKE2S2SV"], "2050");
+    /// ```
+    fn delete_searches<S: AsRef<str>>(
+        &self,
+        searches_keys: &[S],
+        last_version: &str,
+    ) -> Vec<KeyBatch> {
+        batch::key_batches(
+            self,
+            "DELETE",
+            "/searches",
+            "searchKey",
+            searches_keys,
+            None,
+            Some(last_version),
+        )
+    }
+
+    /// Generate Api request to delete a Zotero tag.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let delete_req = z.delete_tag("history", "2050");
+    /// ```
+    fn delete_tag<S: AsRef<str> + std::fmt::Display>(
+        &self,
+        tag_key: S,
+        last_version: S,
+    ) -> Request<Bytes> {
+        let params = format!("/tags?tag={}", tag_key);
+        let mut req = self.request::<_, ()>("DELETE", params, None, None);
+        req.headers_mut().insert(
+            "If-Unmodified-Since-Version",
+            HeaderValue::from_str(last_version.as_ref()).unwrap(),
+        );
+        req
+    }
+
+    /// Generate Api request to delete multiple Zotero tags.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let delete_req = z.delete_tags(vec!["history", "philosophy", "art"], "2050");
+    /// ```
+    fn delete_tags<S: AsRef<str> + std::fmt::Display>(
+        &self,
+        tags_keys: Vec<S>,
+        last_version: S,
+    ) -> Request<Bytes> {
+        let params = format!(
+            "/tags?tag={}",
+            tags_keys
+                .iter()
+                .map(|elem| byte_serialize(elem.as_ref().as_bytes()).collect())
+                .collect::<Vec<String>>()
+                .join(" || ")
+        );
+        let mut req = self.request::<_, ()>("DELETE", params, None, None);
+        req.headers_mut().insert(
+            "If-Unmodified-Since-Version",
+            HeaderValue::from_str(last_version.as_ref()).unwrap(),
+        );
+        req
+    }
+
+    /// Generate Api request to create an item
+    fn create_new_item<T: Serialize>(&self, item: T) -> Request<Bytes> {
+        let params = "/items".to_string();
+        self.request("POST", params, None, Some(&vec![&item]))
+    }
+
+    /// Generate Api request to create multiple items
+    fn create_new_items<T: Serialize>(&self, item: Vec<T>) -> Request<Bytes> {
+        let params = "/items".to_string();
+        self.request("POST", params, None, Some(&item))
+    }
+
+    /// Generate Api request to create new collection
+    fn create_new_collection<T: Serialize>(&self, item: T) -> Request<Bytes> {
+        let params = "/collections".to_string();
+        self.request("POST", params, None, Some(&vec![&item]))
+    }
+
+    /// Generate Api request to create new collections
+    fn create_new_collections<T: Serialize>(&self, item: Vec<T>) -> Request<Bytes> {
+        let params = "/collections".to_string();
+        self.request("POST", params, None, Some(&item))
+    }
+
+    /// Generate Api request to update a zotero item.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite, ZoteroApiExecutor, ZoteroApiError};
+    /// # use zotero_data::item::ItemType;
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let remote_item: Result<ItemType, ZoteroApiError> = z.get_item("B8ZNE3GH", None).execute(&z);
+    ///
+    /// if let Ok(mut result) = remote_item {
+    ///     if let ItemType::Book(bookdata) = &mut result {
+    ///         bookdata.title = "A new title".to_string();
+    ///         bookdata.publisher = "Doe editions".to_string();
+    ///         bookdata.creators = vec!();
+    ///         let _: Result<(), _> = z.update_item(&bookdata.key, &bookdata).execute(&z);
+    ///     };
+    /// };
+    ///```
+    fn update_item<T: Serialize, S: AsRef<str> + std::fmt::Display>(
+        &self,
+        item_key: S,
+        item_data: T,
+    ) -> Request<Bytes> {
+        let params = format!("/items/{}", item_key);
+        self.request("PATCH", params, None, Some(&item_data))
+    }
+
+    /// Generate Api request to update a Zotero collection, e.g. to rename it.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite, ZoteroApiExecutor};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let data = serde_json::json!({"name": "A new name"});
+    /// let _: Result<(), _> = z.update_collection("TYQDGEZR", &data).execute(&z);
+    /// ```
+    fn update_collection<T: Serialize, S: AsRef<str> + std::fmt::Display>(
+        &self,
+        collection_key: S,
+        collection_data: T,
+    ) -> Request<Bytes> {
+        let params = format!("/collections/{}", collection_key);
+        self.request("PATCH", params, None, Some(&collection_data))
+    }
+
+    /// Generate Api request moving a collection under `new_parent`, or to the top level with `None`.
+    /// The request fails if the collection was modified since it was retrieved.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite, ZoteroApiExecutor};
+    /// # use zotero_data::collection::Collection;
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let collection: Collection = z.get_collection("TYQDGEZR", None).execute(&z).unwrap();
+    /// let _: Result<(), _> = z.move_collection(&collection, "AYVWED").unwrap().execute(&z);
+    /// ```
+    fn move_collection<'p, P: Into<Option<&'p str>>>(
+        &self,
+        collection: &Collection,
+        new_parent: P,
+    ) -> Result<Request<Bytes>, ZoteroApiError> {
+        let new_parent: Option<&str> = new_parent.into();
+        if new_parent == Some(collection.key.as_str()) {
+            return Err(ZoteroApiError::RequestCreationError(format!(
+                "collection {} can't be its own parent",
+                collection.key
+            )));
+        }
+        let data = serde_json::json!({
+            "parentCollection": new_parent.map_or(serde_json::Value::Bool(false), serde_json::Value::from)
+        });
+        let mut req = self.update_collection(&collection.key, &data);
+        req.headers_mut().insert(
+            "If-Unmodified-Since-Version",
+            HeaderValue::from(collection.version),
+        );
+        Ok(req)
+    }
+
+    /// Generate Api requests adding items to a collection. Items already in the collection and child items,
+    /// which can't belong to collections, are skipped. Items are updated by batches of 50, and each update
+    /// fails if the item was modified since it was retrieved.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite, ZoteroApiExecutor};
+    /// # use zotero_data::item::Item;
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let items: Vec<Item> = z.get_items("tag=history").execute(&z).unwrap();
+    /// for request in z.add_items_to_collection(&items, "TYQDGEZR") {
+    ///     let _: Result<serde_json::Value, _> = request.execute(&z);
+    /// }
+    /// ```
+    fn add_items_to_collection(&self, items: &[Item], collection_key: &str) -> Vec<Request<Bytes>>
+    where
+        Self: Sized,
+    {
+        membership_requests(self, items, collection_key, true)
+    }
+
+    /// Generate Api requests removing items from a collection, the items themselves are kept. Items that are
+    /// not in the collection are skipped. Items are updated by batches of 50, and each update fails if the
+    /// item was modified since it was retrieved.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite, ZoteroApiExecutor};
+    /// # use zotero_data::item::Item;
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let items: Vec<Item> = z.get_collection_items("TYQDGEZR", None).execute(&z).unwrap();
+    /// for request in z.remove_items_from_collection(&items, "TYQDGEZR") {
+    ///     let _: Result<serde_json::Value, _> = request.execute(&z);
+    /// }
+    /// ```
+    fn remove_items_from_collection(
+        &self,
+        items: &[Item],
+        collection_key: &str,
+    ) -> Vec<Request<Bytes>>
+    where
+        Self: Sized,
+    {
+        membership_requests(self, items, collection_key, false)
+    }
+
+    /// Generate Api requests relating two items with `dc:relation`, like the "Related" pane of Zotero.
+    /// Zotero stores the relation on both items, so one PATCH request is generated for each item, sent to the
+    /// library of that item. Items can belong to different libraries, as long as the API key can write to both.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite, ZoteroApiExecutor};
+    /// # use zotero_data::item::Item;
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let item: Item = z.get_item("B8ZNE3GH", None).execute(&z).unwrap();
+    /// let other: Item = z.get_item("Q8GNE36F", None).execute(&z).unwrap();
+    ///
+    /// for request in z.link_items(&item, &other).unwrap() {
+    ///     let _: Result<(), _> = request.execute(&z);
+    /// }
+    /// ```
+    fn link_items(&self, item: &Item, other: &Item) -> Result<[Request<Bytes>; 2], ZoteroApiError>
+    where
+        Self: Sized,
+    {
+        Ok([
+            relation_request(self, item, other, true)?,
+            relation_request(self, other, item, true)?,
+        ])
+    }
+
+    /// Generate Api requests removing the `dc:relation` between two items, on both sides.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite, ZoteroApiExecutor};
+    /// # use zotero_data::item::Item;
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let item: Item = z.get_item("B8ZNE3GH", None).execute(&z).unwrap();
+    /// let other: Item = z.get_item("Q8GNE36F", None).execute(&z).unwrap();
+    ///
+    /// for request in z.unlink_items(&item, &other).unwrap() {
+    ///     let _: Result<(), _> = request.execute(&z);
+    /// }
+    /// ```
+    fn unlink_items(&self, item: &Item, other: &Item) -> Result<[Request<Bytes>; 2], ZoteroApiError>
+    where
+        Self: Sized,
+    {
+        Ok([
+            relation_request(self, item, other, false)?,
+            relation_request(self, other, item, false)?,
+        ])
+    }
+
+    /// Plan the merge of duplicate items into `master`, like the "Merge items" button of Zotero desktop:
+    /// - empty fields of the master item are filled from the other items, and its creators if it has none;
+    /// - tags, collections and relations of the other items are added to the master item, along with a
+    ///   `dc:replaces` relation to each of them;
+    /// - the `children` of the other items (notes and attachments) are moved to the master item;
+    /// - the other items are moved to the trash.
+    ///
+    /// `children` can contain any item, only the children of `others` are moved. All the items must have the same
+    /// item type. Nothing is sent, the requests of the returned plan still have to be executed.
+    fn plan_merge_items(
+        &self,
+        master: &Item,
+        others: &[Item],
+        children: &[Item],
+    ) -> Result<MergePlan, ZoteroApiError>
+    where
+        Self: Sized,
+    {
+        merge::plan(self, master, others, children)
+    }
+
+    /// Fetch the items and their children, then merge `other_keys` into `master_key` as described in
+    /// [`ZoteroApiWrite::plan_merge_items`]. With `dry_run`, nothing is changed: the returned plan lists the changes
+    /// that would be made, and can be displayed.
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let plan = z.merge_items("B8ZNE3GH", &["Q8GNE36F"], true).unwrap();
+    /// assert_eq!(plan.trashed, vec!["Q8GNE36F"]);
+    /// print!("{}", plan);
+    /// ```
+    fn merge_items<S: AsRef<str> + std::fmt::Display>(
+        &self,
+        master_key: S,
+        other_keys: &[S],
+        dry_run: bool,
+    ) -> Result<MergePlan, ZoteroApiError>
+    where
+        Self: Sized,
+    {
+        let master: Item = self.get_item(master_key, None).execute(self)?;
+        let mut others: Vec<Item> = Vec::new();
+        let mut children: Vec<Item> = Vec::new();
+        for key in other_keys {
+            others.push(self.get_item(key, None).execute(self)?);
+            let mut other_children: Vec<Item> = self.get_child_items(key, None).execute(self)?;
+            children.append(&mut other_children);
+        }
+
+        let mut plan = self.plan_merge_items(&master, &others, &children)?;
+        if !dry_run {
+            for request in plan.requests.drain(..) {
+                let _: serde_json::Value = request.execute(self)?;
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Return the key of the collection at `path`, e.g. `"Thesis/Chapter 2/Sources"`, creating the missing
+    /// collections along the path with [`ZoteroApiWrite::create_new_collections`].
+    /// ```no_run
+    /// # use zotero_api::{Zotero, ZoteroApi, ZoteroApiWrite};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
+    /// let key = z.create_collection_path("Thesis/Chapter 2/Sources").unwrap();
+    /// ```
+    fn create_collection_path(&self, path: &str) -> Result<String, ZoteroApiError>
+    where
+        Self: Sized,
+    {
+        use serde_json::Value;
+
+        let tree = self.get_collection_tree(None)?;
+        let (existing, missing) = tree.missing_path(path);
+        let mut parent = existing.map(|node| node.key().to_string());
+        if parent.is_none() && missing.is_empty() {
+            return Err(ZoteroApiError::RequestCreationError(format!(
+                "invalid collection path \"{}\"",
+                path
+            )));
+        }
+
+        // each collection needs the key of its parent, so they are created one level at a time
+        for name in missing {
+            let data = serde_json::json!({
+                "name": name,
+                "parentCollection": parent.as_deref().map_or(Value::Bool(false), Value::from),
+            });
+            let response: Value = self.create_new_collections(vec![data]).execute(self)?;
+            let key = response["success"]["0"].as_str().ok_or_else(|| {
+                ZoteroApiError::RequestError(format!(
+                    "collection \"{}\" not created: {}",
+                    name, response["failed"]["0"]["message"]
+                ))
+            })?;
+            parent = Some(key.to_string());
+        }
+        parent.ok_or_else(|| ZoteroApiError::RequestCreationError(path.to_string()))
+    }
+}
+
+/// PATCH request setting the `deleted` flag of an item.
+fn trash_request<Z: ZoteroApiWrite + ?Sized, S: AsRef<str> + std::fmt::Display>(
+    zotero_api: &Z,
+    item_key: S,
+    last_version: S,
+    deleted: bool,
+) -> Request<Bytes> {
+    let data = serde_json::json!({ "deleted": u8::from(deleted) });
+    let mut req = zotero_api.update_item(item_key, &data);
+    req.headers_mut().insert(
+        "If-Unmodified-Since-Version",
+        HeaderValue::from_str(last_version.as_ref()).unwrap(),
+    );
+    req
+}
+
+/// POST requests adding (or removing) `collection_key` to the `collections` of the items that need it.
+/// Zotero checks the `version` sent with each item, and at most 50 items can be updated by a request.
+fn membership_requests<Z: ZoteroApi>(
+    zotero_api: &Z,
+    items: &[Item],
+    collection_key: &str,
+    add: bool,
+) -> Vec<Request<Bytes>> {
+    let updates: Vec<serde_json::Value> = items
+        .iter()
+        .filter(|item| item.data.parent_item().is_none_or(str::is_empty))
+        .filter_map(|item| {
+            let mut collections = item.data.collections().to_vec();
+            let is_member = collections.iter().any(|c| c == collection_key);
+            match (add, is_member) {
+                (true, false) => collections.push(collection_key.to_string()),
+                (false, true) => collections.retain(|c| c != collection_key),
+                _ => return None,
+            }
+            Some(serde_json::json!({
+                "key": item.key,
+                "version": item.version,
+                "collections": collections,
+            }))
+        })
+        .collect();
+
+    updates
+        .chunks(ZOTERO_WRITE_LIMIT)
+        .map(|batch| zotero_api.request("POST", "/items".to_string(), None, Some(&batch.to_vec())))
+        .collect()
+}
+
+/// PATCH request adding (or removing) `other` to the `dc:relation` of `item`, sent to the library of `item`.
+/// Zotero replaces the whole `relations` object on PATCH, so the existing relations are sent along.
+fn relation_request<Z: ZoteroApi>(
+    zotero_api: &Z,
+    item: &Item,
+    other: &Item,
+    link: bool,
+) -> Result<Request<Bytes>, ZoteroApiError> {
+    let uri = |item: &Item| {
+        item.uri().ok_or_else(|| {
+            ZoteroApiError::RequestCreationError(format!(
+                "unknown library type \"{}\" for item {}",
+                item.library.r#type, item.key
+            ))
+        })
+    };
+    let item_uri = uri(item)?;
+    let other_uri = uri(other)?;
+
+    let mut relations = item.relations();
+    match link {
+        true => relations.add(RelationPredicate::Relation, &other_uri),
+        false => relations.remove(RelationPredicate::Relation, &other_uri.to_string()),
+    };
+
+    let url = format!(
+        "{}{}/{}/items/{}",
+        ZOTERO_BASE_URL,
+        item_uri.library_kind.path_segment(),
+        item_uri.library_id,
+        item.key
+    );
+    let data = serde_json::json!({ "relations": relations });
+    let mut req = zotero_api.request_uri("PATCH", url);
+    *req.body_mut() = Bytes::from(serde_json::to_vec(&data).unwrap());
+    req.headers_mut().insert(
+        "If-Unmodified-Since-Version",
+        HeaderValue::from(item.version),
+    );
+    Ok(req)
+}
@@ -0,0 +1,128 @@
+//! Requests on many objects at once, split into batches of 50 keys: the most the Zotero API accepts.
+
+use bytes::Bytes;
+use http::{header::HeaderValue, Request};
+use serde_json::Value;
+
+use crate::consts::ZOTERO_WRITE_LIMIT;
+use crate::executor::execute_versioned;
+use crate::transport::HttpTransport;
+use crate::{ZoteroApi, ZoteroApiError};
+
+/// A request on up to 50 objects, with their keys.
+#[derive(Debug)]
+pub struct KeyBatch {
+    pub keys: Vec<String>,
+    pub request: Request<Bytes>,
+}
+
+/// A batch that failed, with the keys it was sent for.
+#[derive(Debug)]
+pub struct BatchFailure {
+    pub keys: Vec<String>,
+    pub error: ZoteroApiError,
+}
+
+/// The merged outcome of a list of [`KeyBatch`]es. A failed batch doesn't stop the following ones.
+#[derive(Debug)]
+pub struct BatchResult<T = Value> {
+    /// Objects returned by the successful batches, in order. Empty for deletions.
+    pub results: Vec<T>,
+    /// Keys of the successful batches.
+    pub succeeded: Vec<String>,
+    pub failures: Vec<BatchFailure>,
+}
+
+impl<T> Default for BatchResult<T> {
+    fn default() -> BatchResult<T> {
+        BatchResult {
+            results: Vec::new(),
+            succeeded: Vec::new(),
+            failures: Vec::new(),
+        }
+    }
+}
+
+impl<T> BatchResult<T> {
+    /// Whether every batch succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Keys of the failed batches.
+    pub fn failed_keys(&self) -> impl Iterator<Item = &str> {
+        self.failures
+            .iter()
+            .flat_map(|failure| failure.keys.iter().map(String::as_str))
+    }
+}
+
+/// One request per group of 50 keys, listed in the `key_param` query parameter of `path`.
+pub(crate) fn key_batches<Z: ZoteroApi + ?Sized, S: AsRef<str>>(
+    zotero_api: &Z,
+    method: &str,
+    path: &str,
+    key_param: &str,
+    keys: &[S],
+    extra_params: Option<&str>,
+    last_version: Option<&str>,
+) -> Vec<KeyBatch> {
+    let keys: Vec<String> = keys.iter().map(|key| key.as_ref().to_string()).collect();
+    keys.chunks(ZOTERO_WRITE_LIMIT)
+        .map(|keys| {
+            let mut params = format!("{}?{}={}", path, key_param, keys.join(","));
+            if let Some(extra_params) = extra_params {
+                params.push('&');
+                params.push_str(extra_params);
+            }
+            let mut request = zotero_api.request::<_, ()>(method, params, None, None);
+            if let Some(last_version) = last_version {
+                request.headers_mut().insert(
+                    "If-Unmodified-Since-Version",
+                    HeaderValue::from_str(last_version).unwrap(),
+                );
+            }
+            KeyBatch {
+                keys: keys.to_vec(),
+                request,
+            }
+        })
+        .collect()
+}
+
+/// Execute the batches in order. Zotero bumps the library version on every write, so the
+/// `If-Unmodified-Since-Version` of a batch is updated with the version returned by the previous one.
+pub(crate) fn execute<T: serde::de::DeserializeOwned>(
+    transport: &dyn HttpTransport,
+    batches: Vec<KeyBatch>,
+) -> BatchResult<T> {
+    let mut result = BatchResult::default();
+    let mut library_version: Option<usize> = None;
+    for KeyBatch { keys, mut request } in batches {
+        if let Some(version) = library_version {
+            if let Some(header) = request.headers_mut().get_mut("If-Unmodified-Since-Version") {
+                *header = HeaderValue::from(version);
+            }
+        }
+        let response = execute_versioned(transport, request).and_then(|(body, version)| {
+            let objects = match body {
+                Value::Array(objects) => objects
+                    .into_iter()
+                    .map(serde_json::from_value)
+                    .collect::<Result<Vec<T>, _>>()
+                    .map_err(|err| ZoteroApiError::ParseResponseError(err.to_string()))?,
+                _ => Vec::new(),
+            };
+            Ok((objects, version))
+        });
+        match response {
+            Ok((mut objects, version)) => {
+                result.results.append(&mut objects);
+                result.succeeded.extend(keys);
+                library_version = Some(version);
+            }
+            Err(error) => result.failures.push(BatchFailure { keys, error }),
+        }
+    }
+    result
+}
@@ -1 +1,4 @@
 pub static ZOTERO_BASE_URL: &str = "https://api.zotero.org/";
+
+/// Maximum number of objects in a single write request.
+pub static ZOTERO_WRITE_LIMIT: usize = 50;
@@ -0,0 +1,181 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{header::LINK, HeaderMap, Request, Response};
+use serde_json::Value;
+
+use crate::transport::HttpTransport;
+use crate::{ZoteroApiAsyncExecutor, ZoteroApiError, ZoteroApiExecutor};
+
+impl ZoteroApiExecutor for Request<Bytes> {
+    fn execute<'a, T: serde::Deserialize<'a>, Z: crate::ZoteroApi>(
+        self,
+        zotero_api: &Z,
+    ) -> Result<T, crate::ZoteroApiError> {
+        let transport = zotero_api.transport();
+        let res = check_status(transport.send(self)?)?;
+        let mut next_page = get_next_page(res.headers());
+        let response = parse_body(res.body())?;
+
+        match next_page {
+            None => T::deserialize(response)
+                .map_err(|err| ZoteroApiError::ParseResponseError(err.to_string())),
+            Some(_) => {
+                let mut responses = page_values(response);
+
+                // follow pagination if any
+                while let Some(np) = next_page {
+                    let res = check_status(transport.send(zotero_api.request_uri("GET", np))?)?;
+                    next_page = get_next_page(res.headers());
+                    responses.append(&mut page_values(parse_body(res.body())?));
+                }
+
+                T::deserialize(Value::Array(responses))
+                    .map_err(|err| ZoteroApiError::ParseResponseError(err.to_string()))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ZoteroApiAsyncExecutor for Request<Bytes> {
+    async fn execute<'a, T: serde::Deserialize<'a>, Z: crate::ZoteroApi + std::marker::Sync>(
+        self,
+        zotero_api: &Z,
+    ) -> Result<T, crate::ZoteroApiError> {
+        let transport = zotero_api.async_transport();
+        let res = check_status(transport.send(self).await?)?;
+        let mut next_page = get_next_page(res.headers());
+        let response = parse_body(res.body())?;
+
+        match next_page {
+            None => T::deserialize(response)
+                .map_err(|err| ZoteroApiError::ParseResponseError(err.to_string())),
+            Some(_) => {
+                let mut responses = page_values(response);
+
+                // follow pagination if any
+                while let Some(np) = next_page {
+                    let request = zotero_api.request_uri("GET", np);
+                    let res = check_status(transport.send(request).await?)?;
+                    next_page = get_next_page(res.headers());
+                    responses.append(&mut page_values(parse_body(res.body())?));
+                }
+
+                T::deserialize(Value::Array(responses))
+                    .map_err(|err| ZoteroApiError::ParseResponseError(err.to_string()))
+            }
+        }
+    }
+}
+
+/// Execute a single request, without following pagination, and return its body along with the
+/// `Last-Modified-Version` of the library, needed to chain version-checked writes.
+pub(crate) fn execute_versioned(
+    transport: &dyn HttpTransport,
+    request: Request<Bytes>,
+) -> Result<(Value, usize), ZoteroApiError> {
+    let res = check_status(transport.send(request)?)?;
+    let version = res
+        .headers()
+        .get("Last-Modified-Version")
+        .and_then(|version| version.to_str().ok()?.parse().ok())
+        .ok_or_else(|| {
+            ZoteroApiError::ParseResponseError("missing Last-Modified-Version header".to_string())
+        })?;
+    let body = parse_body(res.body())?;
+    Ok((body, version))
+}
+
+fn check_status(res: Response<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+    let text = || String::from_utf8_lossy(res.body()).into_owned();
+
+    if res.status().as_u16() == 403 {
+        return Err(ZoteroApiError::AuthenticationError(text()));
+    }
+
+    if !res.status().is_success() {
+        return Err(ZoteroApiError::RequestError(format!(
+            "{}: {}",
+            res.status(),
+            text()
+        )));
+    }
+
+    Ok(res)
+}
+
+/// Write requests answer with an empty body (`204 No Content`), parsed as `null`.
+fn parse_body(body: &[u8]) -> Result<Value, ZoteroApiError> {
+    match body.is_empty() {
+        true => Ok(Value::Null),
+        false => serde_json::from_slice(body)
+            .map_err(|err| ZoteroApiError::ParseResponseError(err.to_string())),
+    }
+}
+
+/// The objects of a page of results.
+fn page_values(page: Value) -> Vec<Value> {
+    match page {
+        Value::Array(values) => values,
+        value => vec![value],
+    }
+}
+
+/// The `next` link of a `Link` header such as `<https://…&start=50>; rel="next", <https://…>; rel="last"`.
+fn get_next_page(headers: &HeaderMap) -> Option<String> {
+    let mut links = headers.get(LINK)?.to_str().ok()?;
+    // targets are split on `<` and `>` rather than `,`, which may appear in their query
+    while let Some(start) = links.find('<') {
+        let end = start + links[start..].find('>')?;
+        let target = &links[start + 1..end];
+        links = &links[end + 1..];
+        let params = &links[..links.find('<').unwrap_or(links.len())];
+        let is_next = params
+            .split(';')
+            .filter_map(|param| param.split_once('='))
+            .any(|(name, value)| {
+                name.trim().eq_ignore_ascii_case("rel")
+                    && value
+                        .trim()
+                        .trim_matches(|c| c == '"' || c == ',')
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("next"))
+            });
+        if is_next {
+            return Some(target.to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_page() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(get_next_page(&headers), None);
+
+        headers.insert(
+            LINK,
+            "<https://api.zotero.org/users/1/items?itemKey=A,B&start=25>; rel=\"next\", \
+             <https://api.zotero.org/users/1/items?start=75>; rel=\"last\""
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            get_next_page(&headers).unwrap(),
+            "https://api.zotero.org/users/1/items?itemKey=A,B&start=25"
+        );
+
+        headers.insert(
+            LINK,
+            "<https://api.zotero.org/users/1/items?start=0>; rel=\"first\", \
+             <https://api.zotero.org/users/1/items>; rel=\"alternate\""
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(get_next_page(&headers), None);
+    }
+}
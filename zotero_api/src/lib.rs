@@ -9,7 +9,7 @@
 //! ## Creating items and collections
 //!
 //! ```no_run
-//! use zotero_api::{Zotero, ZoteroApi, ZoteroApiExecutor};
+//! use zotero_api::{Zotero, ZoteroApi, ZoteroApiExecutor, ZoteroApiWrite};
 //! use zotero_data::item::{BookData, BookDataBuilder, Creator, CreatorBuilder};
 //!
 //! let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
@@ -26,7 +26,6 @@
 //! let new_book : BookData = BookDataBuilder::default()
 //!     .title("Sample_2")
 //!     .creators(creators)
-//!     .item_type("book")
 //!     .build()
 //!     .unwrap();
 //!
@@ -36,7 +35,7 @@
 //! ## Updating items and collections
 //!
 //! ```no_run
-//! use zotero_api::{Zotero, ZoteroApi, ZoteroApiExecutor};
+//! use zotero_api::{Zotero, ZoteroApi, ZoteroApiExecutor, ZoteroApiWrite};
 //! use zotero_data::item::ItemType;
 //!
 //! let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
@@ -53,16 +52,25 @@
 //! ```
 
 mod api_request;
+mod api_write;
+mod batch;
 mod consts;
-
-#[cfg(feature = "reqwest")]
-mod reqwest_impl;
+mod executor;
+mod merge;
+pub mod transport;
 
 use async_trait::async_trait;
 use serde::Deserialize;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
 use thiserror::Error;
 
 pub use crate::api_request::ZoteroApi;
+pub use crate::api_write::ZoteroApiWrite;
+pub use crate::batch::{BatchFailure, BatchResult, KeyBatch};
+pub use crate::merge::MergePlan;
+pub use crate::transport::{AsyncHttpTransport, HttpTransport, MockTransport};
 
 #[derive(Debug, Error)]
 pub enum ZoteroApiError {
@@ -77,7 +85,7 @@ pub enum ZoteroApiError {
 }
 
 pub trait ZoteroApiExecutor {
-    fn execute<'a, T: Deserialize<'a>, Z: ZoteroApi<'a>>(
+    fn execute<'a, T: Deserialize<'a>, Z: ZoteroApi>(
         self,
         zotero_api: &Z,
     ) -> Result<T, ZoteroApiError>;
@@ -85,20 +93,83 @@ pub trait ZoteroApiExecutor {
 
 #[async_trait]
 pub trait ZoteroApiAsyncExecutor {
-    async fn execute<'a, T: Deserialize<'a>, Z: ZoteroApi<'a> + std::marker::Sync>(
+    async fn execute<'a, T: Deserialize<'a>, Z: ZoteroApi + std::marker::Sync>(
         self,
         zotero_api: &Z,
     ) -> Result<T, ZoteroApiError>;
 }
 
-/// A struct representing a Zotero client.
-#[derive(Debug, PartialEq)]
-pub struct Zotero<'a> {
-    pub library_type: LibraryType<'a>,
+/// Access marker of a client that can only read its library: write requests can't be built with it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ReadOnly;
+
+/// Access marker of a client allowed to modify its library, see [`ZoteroApiWrite`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ReadWrite;
+
+/// An optional API key, borrowed or owned: `"key"`, `String`, `Some("key")` or `None`.
+///
+/// `Option<String>` is left out so that a bare `None` needs no type annotation: pass an optional owned key
+/// with `.as_deref()`.
+pub trait IntoApiKey {
+    fn into_api_key(self) -> Option<String>;
+}
+
+impl IntoApiKey for &str {
+    fn into_api_key(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl IntoApiKey for String {
+    fn into_api_key(self) -> Option<String> {
+        Some(self)
+    }
+}
+
+impl IntoApiKey for &String {
+    fn into_api_key(self) -> Option<String> {
+        Some(self.clone())
+    }
+}
+
+impl IntoApiKey for Option<&str> {
+    fn into_api_key(self) -> Option<String> {
+        self.map(str::to_string)
+    }
+}
+
+/// A struct representing a Zotero client. Only `Zotero<ReadWrite>` clients can build write requests.
+///
+/// The client owns its IDs and API key: it can be cloned, shared in an `Arc` or moved to another thread or task.
+/// Requests are sent through the transports of the client, see [`transport`].
+#[derive(Clone)]
+pub struct Zotero<A = ReadWrite> {
+    pub library_type: LibraryType,
+    transport: Arc<dyn HttpTransport>,
+    async_transport: Arc<dyn AsyncHttpTransport>,
+    access: PhantomData<A>,
+}
+
+impl<A> fmt::Debug for Zotero<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Zotero")
+            .field("library_type", &self.library_type)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Clients are equal when they target the same library with the same API key, whatever their transports.
+impl<A> PartialEq for Zotero<A> {
+    fn eq(&self, other: &Zotero<A>) -> bool {
+        self.library_type == other.library_type
+    }
 }
 
-impl<'a> Zotero<'a> {
-    /// Create a Zotero client for a group library. According to group policy API key might be optional.
+impl Zotero {
+    /// Create a read-only Zotero client for a group library. According to group policy API key might be optional.
+    /// Call [`Zotero::check_write_access`], or [`Zotero::assume_write_access`] without checking the API key, to
+    /// modify the library.
     /// ```rust
     /// # use zotero_api::Zotero;
     /// // With an API key
@@ -106,11 +177,13 @@ impl<'a> Zotero<'a> {
     ///
     /// // Without API key
     /// let z = Zotero::set_group("123456789", None);
+    ///
+    /// // With an owned, optional API key
+    /// let api_key: Option<String> = std::env::var("ZOTERO_API_KEY").ok();
+    /// let z = Zotero::set_group("123456789", api_key.as_deref());
     /// ```
-    pub fn set_group<S: Into<Option<&'a str>>>(group_id: &'a str, api_key: S) -> Zotero<'a> {
-        Zotero {
-            library_type: LibraryType::group(group_id, api_key),
-        }
+    pub fn set_group<G: Into<String>, K: IntoApiKey>(group_id: G, api_key: K) -> Zotero<ReadOnly> {
+        Zotero::new(LibraryType::group(group_id, api_key))
     }
 
     /// Create a Zotero client for a user library.
@@ -118,52 +191,147 @@ impl<'a> Zotero<'a> {
     /// # use zotero_api::Zotero;
     /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ");
     /// ```
-    pub fn set_user(user_id: &'a str, api_key: &'a str) -> Zotero<'a> {
+    pub fn set_user<U: Into<String>, K: Into<String>>(user_id: U, api_key: K) -> Zotero {
+        Zotero::new(LibraryType::user(user_id, api_key))
+    }
+}
+
+impl<A> Zotero<A> {
+    /// A client using the default transports: reqwest if enabled, else ureq (blocking) and hyper (async).
+    fn new(library_type: LibraryType) -> Zotero<A> {
+        Zotero {
+            library_type,
+            transport: transport::default_transport(),
+            async_transport: transport::default_async_transport(),
+            access: PhantomData,
+        }
+    }
+
+    /// Send the requests executed with [`ZoteroApiExecutor`] through `transport`.
+    /// ```rust
+    /// # use zotero_api::{MockTransport, Zotero};
+    /// let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ").with_transport(MockTransport::new());
+    /// ```
+    pub fn with_transport<T: HttpTransport + 'static>(self, transport: T) -> Zotero<A> {
+        Zotero {
+            transport: Arc::new(transport),
+            ..self
+        }
+    }
+
+    /// Send the requests executed with [`ZoteroApiAsyncExecutor`] through `transport`.
+    pub fn with_async_transport<T: AsyncHttpTransport + 'static>(self, transport: T) -> Zotero<A> {
+        Zotero {
+            async_transport: Arc::new(transport),
+            ..self
+        }
+    }
+
+    /// The same client, without write access.
+    pub fn read_only(self) -> Zotero<ReadOnly> {
+        self.with_access()
+    }
+
+    fn with_access<B>(self) -> Zotero<B> {
         Zotero {
-            library_type: LibraryType::user(user_id, api_key),
+            library_type: self.library_type,
+            transport: self.transport,
+            async_transport: self.async_transport,
+            access: PhantomData,
         }
     }
 }
 
-impl<'a> ZoteroApi<'a> for Zotero<'a> {
-    fn get_id(&self) -> &'a str {
+impl Zotero<ReadOnly> {
+    /// Allow write requests without checking the permissions of the API key: writes fail later, with an
+    /// authentication error, if the key can't write to the library. Fails only if the client has no API key.
+    /// Prefer [`Zotero::check_write_access`] unless the key is known to have write access.
+    /// ```rust
+    /// # use zotero_api::{Zotero, ZoteroApiWrite};
+    /// let z = Zotero::set_group("123456789", "bZARysJ579K5SdmYuaAJ").assume_write_access().unwrap();
+    /// let delete_req = z.delete_item("PJTUB2WE", "2050");
+    ///
+    /// assert!(Zotero::set_group("123456789", None).assume_write_access().is_err());
+    /// ```
+    pub fn assume_write_access(self) -> Result<Zotero<ReadWrite>, ZoteroApiError> {
+        match self.library_type.get_api_key() {
+            Some(_) => Ok(self.with_access()),
+            None => Err(ZoteroApiError::AuthenticationError(format!(
+                "writing to library {} requires an API key",
+                self.library_type.get_id()
+            ))),
+        }
+    }
+
+    /// Allow write requests after checking the permissions of the API key with the Zotero API.
+    /// ```no_run
+    /// # use zotero_api::Zotero;
+    /// let z = Zotero::set_group("123456789", "bZARysJ579K5SdmYuaAJ").check_write_access().unwrap();
+    /// ```
+    pub fn check_write_access(self) -> Result<Zotero<ReadWrite>, ZoteroApiError> {
+        let key_info: serde_json::Value = match self.get_api_key_info(None) {
+            Some(request) => ZoteroApiExecutor::execute(request, &self)?,
+            None => serde_json::Value::Null,
+        };
+        match self.library_type.has_write_access(&key_info) {
+            true => self.assume_write_access(),
+            false => Err(ZoteroApiError::AuthenticationError(format!(
+                "the API key has no write access to library {}",
+                self.library_type.get_id()
+            ))),
+        }
+    }
+}
+
+impl<A> ZoteroApi for Zotero<A> {
+    fn get_id(&self) -> &str {
         self.library_type.get_id()
     }
 
-    fn get_api_key(&self) -> Option<&'a str> {
+    fn get_api_key(&self) -> Option<&str> {
         self.library_type.get_api_key()
     }
 
     fn get_base_url(&self) -> String {
         self.library_type.get_base_url()
     }
+
+    fn transport(&self) -> &dyn HttpTransport {
+        &*self.transport
+    }
+
+    fn async_transport(&self) -> &dyn AsyncHttpTransport {
+        &*self.async_transport
+    }
 }
 
+impl ZoteroApiWrite for Zotero<ReadWrite> {}
+
 /// Either a User library or a Group library
-#[derive(Debug, PartialEq)]
-pub enum LibraryType<'a> {
+#[derive(Debug, PartialEq, Clone)]
+pub enum LibraryType {
     UserLibrary {
-        user_id: &'a str,
-        api_key: &'a str,
+        user_id: String,
+        api_key: String,
     },
     GroupLibrary {
-        group_id: &'a str,
-        api_key: Option<&'a str>,
+        group_id: String,
+        api_key: Option<String>,
     },
 }
 
-impl<'a> LibraryType<'a> {
-    fn get_id(&self) -> &'a str {
+impl LibraryType {
+    fn get_id(&self) -> &str {
         match self {
             LibraryType::UserLibrary { user_id, .. } => user_id,
             LibraryType::GroupLibrary { group_id, .. } => group_id,
         }
     }
 
-    fn get_api_key(&self) -> Option<&'a str> {
+    fn get_api_key(&self) -> Option<&str> {
         match self {
-            LibraryType::UserLibrary { api_key, .. } => Some(*api_key),
-            LibraryType::GroupLibrary { api_key, .. } => api_key.to_owned(),
+            LibraryType::UserLibrary { api_key, .. } => Some(api_key),
+            LibraryType::GroupLibrary { api_key, .. } => api_key.as_deref(),
         }
     }
 
@@ -178,15 +346,35 @@ impl<'a> LibraryType<'a> {
         }
     }
 
-    fn group<S: Into<Option<&'a str>>>(group_id: &'a str, api_key: S) -> LibraryType<'a> {
+    /// Whether the key information returned by `/keys/{key}` grants write access to this library.
+    fn has_write_access(&self, key_info: &serde_json::Value) -> bool {
+        let access = &key_info["access"];
+        match self {
+            LibraryType::UserLibrary { user_id, .. } => {
+                let same_user = user_id
+                    .parse::<u64>()
+                    .is_ok_and(|user_id| key_info["userID"] == user_id);
+                same_user && access["user"]["write"] == true
+            }
+            LibraryType::GroupLibrary { group_id, .. } => {
+                access["groups"][group_id.as_str()]["write"] == true
+                    || access["groups"]["all"]["write"] == true
+            }
+        }
+    }
+
+    fn group<G: Into<String>, K: IntoApiKey>(group_id: G, api_key: K) -> LibraryType {
         LibraryType::GroupLibrary {
-            group_id,
-            api_key: api_key.into(),
+            group_id: group_id.into(),
+            api_key: api_key.into_api_key(),
         }
     }
 
-    fn user(user_id: &'a str, api_key: &'a str) -> LibraryType<'a> {
-        LibraryType::UserLibrary { user_id, api_key }
+    fn user<U: Into<String>, K: Into<String>>(user_id: U, api_key: K) -> LibraryType {
+        LibraryType::UserLibrary {
+            user_id: user_id.into(),
+            api_key: api_key.into(),
+        }
     }
 }
 
@@ -196,22 +384,18 @@ mod tests {
 
     #[test]
     fn test_prepare_zotero() {
-        let expected_struct0 = Zotero {
-            library_type: LibraryType::GroupLibrary {
-                group_id: "123456",
-                api_key: None,
-            },
-        };
+        let expected_struct0 = Zotero::new(LibraryType::GroupLibrary {
+            group_id: "123456".to_string(),
+            api_key: None,
+        });
 
         let result_0 = Zotero::set_group("123456", None);
         assert_eq!(result_0, expected_struct0);
 
-        let expected_struct1 = Zotero {
-            library_type: LibraryType::UserLibrary {
-                user_id: "123456",
-                api_key: "abc",
-            },
-        };
+        let expected_struct1 = Zotero::new(LibraryType::UserLibrary {
+            user_id: "123456".to_string(),
+            api_key: "abc".to_string(),
+        });
 
         let result_1 = Zotero::set_user("123456", "abc");
         assert_eq!(result_1, expected_struct1);
@@ -219,34 +403,32 @@ mod tests {
 
     #[test]
     fn test_group_new() {
-        let expected_struct0 = Zotero {
-            library_type: LibraryType::GroupLibrary {
-                group_id: "456",
-                api_key: Some("123"),
-            },
-        };
+        let expected_struct0 = Zotero::new(LibraryType::GroupLibrary {
+            group_id: "456".to_string(),
+            api_key: Some("123".to_string()),
+        });
 
         let result_0 = Zotero::set_group("456", Some("123"));
 
         assert_eq!(result_0, expected_struct0);
+        assert_eq!(
+            Zotero::set_group("456".to_string(), "123".to_string()),
+            expected_struct0
+        );
 
-        let expected_struct1 = Zotero {
-            library_type: LibraryType::GroupLibrary {
-                group_id: "456",
-                api_key: Some("123"),
-            },
-        };
+        let expected_struct1 = Zotero::new(LibraryType::GroupLibrary {
+            group_id: "456".to_string(),
+            api_key: Some("123".to_string()),
+        });
 
         let result_1 = Zotero::set_group("456", "123");
 
         assert_eq!(result_1, expected_struct1);
 
-        let expected_struct2 = Zotero {
-            library_type: LibraryType::GroupLibrary {
-                group_id: "456",
-                api_key: None,
-            },
-        };
+        let expected_struct2 = Zotero::new(LibraryType::GroupLibrary {
+            group_id: "456".to_string(),
+            api_key: None,
+        });
 
         let result_2 = Zotero::set_group("456", None);
 
@@ -256,8 +438,8 @@ mod tests {
     #[test]
     fn test_library_type() {
         let user_library = LibraryType::UserLibrary {
-            user_id: "123456789",
-            api_key: "abcdef",
+            user_id: "123456789".to_string(),
+            api_key: "abcdef".to_string(),
         };
 
         assert_eq!(user_library.get_id(), "123456789");
@@ -268,8 +450,8 @@ mod tests {
         );
 
         let group_id = LibraryType::GroupLibrary {
-            group_id: "123456789",
-            api_key: Some("abcdef"),
+            group_id: "123456789".to_string(),
+            api_key: Some("abcdef".to_string()),
         };
 
         assert_eq!(group_id.get_id(), "123456789");
@@ -279,4 +461,34 @@ mod tests {
             "https://api.zotero.org/groups/123456789"
         );
     }
+    #[test]
+    fn test_write_access() {
+        let user_library = LibraryType::user("123456789", "abcdef");
+        let key_info = serde_json::json!({
+            "key": "abcdef",
+            "userID": 123456789,
+            "access": {"user": {"library": true, "write": true}, "groups": {"987": {"library": true, "write": false}}}
+        });
+        assert!(user_library.has_write_access(&key_info));
+        assert!(!LibraryType::user("111", "abcdef").has_write_access(&key_info));
+        assert!(!LibraryType::group("987", "abcdef").has_write_access(&key_info));
+        assert!(!LibraryType::group("987", "abcdef").has_write_access(&serde_json::Value::Null));
+
+        let key_info = serde_json::json!({
+            "userID": 123456789,
+            "access": {"groups": {"all": {"library": true, "write": true}}}
+        });
+        assert!(LibraryType::group("987", "abcdef").has_write_access(&key_info));
+        assert!(!user_library.has_write_access(&key_info));
+    }
+
+    #[test]
+    fn test_read_only() {
+        let z = Zotero::set_user("123456", "abc").read_only();
+        assert_eq!(z.get_api_key(), Some("abc"));
+        assert!(z.assume_write_access().is_ok());
+        assert!(Zotero::set_group("456", None)
+            .assume_write_access()
+            .is_err());
+    }
 }
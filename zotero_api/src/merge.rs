@@ -0,0 +1,186 @@
+//! Merging duplicate items, the way the "Merge items" button of Zotero desktop does.
+
+use bytes::Bytes;
+use http::{header::HeaderValue, Request};
+use serde_json::Value;
+use std::fmt;
+use zotero_data::item::{diff, Item, ItemPatch, ItemType};
+use zotero_data::relations::RelationPredicate;
+use zotero_data::shared_fields::{ItemCommon, ItemFields};
+
+use crate::{ZoteroApiError, ZoteroApiWrite};
+
+/// The changes needed to merge items into a master item, with the requests applying them.
+///
+/// Printing a plan shows every planned change, which is what a dry run of
+/// [`ZoteroApiWrite::merge_items`] does.
+#[derive(Debug)]
+pub struct MergePlan {
+    pub master_key: String,
+    /// Changes made to the master item: empty fields filled from the other items, tags, collections and relations.
+    pub master_patch: ItemPatch,
+    /// Keys of the child notes and attachments moved to the master item, with the key of their former parent.
+    pub moved_children: Vec<(String, String)>,
+    /// Keys of the items moved to the trash.
+    pub trashed: Vec<String>,
+    /// PATCH requests to send, in order.
+    pub requests: Vec<Request<Bytes>>,
+}
+
+impl fmt::Display for MergePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.master_patch.is_empty() {
+            true => writeln!(f, "Keep {} unchanged", self.master_key)?,
+            false => {
+                writeln!(f, "Update {}:", self.master_key)?;
+                for change in &self.master_patch.changes {
+                    writeln!(f, "  {}", change)?;
+                }
+            }
+        }
+        for (child, parent) in &self.moved_children {
+            writeln!(f, "Move {} from {} to {}", child, parent, self.master_key)?;
+        }
+        for key in &self.trashed {
+            writeln!(f, "Move {} to the trash", key)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn plan<Z: ZoteroApiWrite>(
+    zotero_api: &Z,
+    master: &Item,
+    others: &[Item],
+    children: &[Item],
+) -> Result<MergePlan, ZoteroApiError> {
+    if others.is_empty() {
+        return Err(ZoteroApiError::RequestCreationError(
+            "no item to merge".to_string(),
+        ));
+    }
+    for other in others {
+        if other.key == master.key {
+            return Err(ZoteroApiError::RequestCreationError(format!(
+                "item {} can't be merged into itself",
+                master.key
+            )));
+        }
+        if other.data.kind() != master.data.kind() || master.data.kind().is_none() {
+            return Err(ZoteroApiError::RequestCreationError(format!(
+                "items {} and {} don't have the same item type",
+                master.key, other.key
+            )));
+        }
+    }
+
+    let merged = merged_data(master, others)?;
+    let master_patch = diff(&master.data, &merged);
+
+    let mut requests = Vec::new();
+    if !master_patch.is_empty() {
+        requests.push(patch_request(zotero_api, master, &master_patch));
+    }
+
+    let mut moved_children = Vec::new();
+    for child in children {
+        if let Some(parent) = child.data.parent_item() {
+            if others.iter().any(|other| other.key == parent) {
+                let data = serde_json::json!({ "parentItem": master.key });
+                requests.push(patch_request(zotero_api, child, &data));
+                moved_children.push((child.key.clone(), parent.to_string()));
+            }
+        }
+    }
+
+    let mut trashed = Vec::new();
+    for other in others {
+        requests.push(zotero_api.trash_item(&other.key, &other.version.to_string()));
+        trashed.push(other.key.clone());
+    }
+
+    Ok(MergePlan {
+        master_key: master.key.clone(),
+        master_patch,
+        moved_children,
+        trashed,
+        requests,
+    })
+}
+
+/// The data of the master item once the other items are merged into it.
+fn merged_data(master: &Item, others: &[Item]) -> Result<ItemType, ZoteroApiError> {
+    let mut merged = master.data.clone();
+
+    for other in others {
+        for (name, value) in other.data.fields() {
+            let is_empty = merged.get_field(name).is_some_and(str::is_empty);
+            if is_empty && !value.is_empty() {
+                let _ = merged.set_field(name, value);
+            }
+        }
+        if let Some(creators) = merged.creators_mut() {
+            if creators.is_empty() {
+                creators.extend(other.data.creators().iter().cloned());
+            }
+        }
+        if let Some(collections) = merged.collections_mut() {
+            for collection in other.data.collections() {
+                if !collections.contains(collection) {
+                    collections.push(collection.clone());
+                }
+            }
+        }
+    }
+
+    // tags and relations have no mutable accessors, they are merged on the JSON representation
+    let mut relations = master.relations();
+    let mut tags: Vec<Value> = serde_json::to_value(master.data.tags())
+        .ok()
+        .and_then(|tags| tags.as_array().cloned())
+        .unwrap_or_default();
+    let master_uri = master.uri().map(|uri| uri.to_string());
+    for other in others {
+        let other_uri = other.uri().ok_or_else(|| {
+            ZoteroApiError::RequestCreationError(format!(
+                "unknown library type \"{}\" for item {}",
+                other.library.r#type, other.key
+            ))
+        })?;
+        relations.add(RelationPredicate::Replaces, &other_uri);
+        for (predicate, uris) in other.relations().iter() {
+            for uri in uris {
+                if Some(uri) != master_uri.as_ref() {
+                    relations.add(predicate.clone(), uri);
+                }
+            }
+        }
+        for tag in other.data.tags() {
+            let tag = serde_json::to_value(tag).unwrap_or_default();
+            if !tags.iter().any(|t| t.get("tag") == tag.get("tag")) {
+                tags.push(tag);
+            }
+        }
+    }
+
+    let mut value = serde_json::to_value(&merged)
+        .map_err(|err| ZoteroApiError::RequestCreationError(err.to_string()))?;
+    value["tags"] = Value::Array(tags);
+    value["relations"] = serde_json::to_value(&relations)
+        .map_err(|err| ZoteroApiError::RequestCreationError(err.to_string()))?;
+    serde_json::from_value(value)
+        .map_err(|err| ZoteroApiError::RequestCreationError(err.to_string()))
+}
+
+fn patch_request<Z: ZoteroApiWrite, T: serde::Serialize>(
+    zotero_api: &Z,
+    item: &Item,
+    data: &T,
+) -> Request<Bytes> {
+    let mut req = zotero_api.update_item(&item.key, data);
+    req.headers_mut().insert(
+        "If-Unmodified-Since-Version",
+        HeaderValue::from(item.version),
+    );
+    req
+}
@@ -0,0 +1,107 @@
+//! HTTP clients sending the requests built by [`ZoteroApi`](crate::ZoteroApi).
+//!
+//! Requests are plain `http::Request<Bytes>`, sent through the [`HttpTransport`] (blocking) or
+//! [`AsyncHttpTransport`] of the client. Each HTTP library is behind a feature:
+//!
+//! | feature | blocking | async |
+//! |---|---|---|
+//! | `reqwest` (default) | [`ReqwestTransport`] | [`AsyncReqwestTransport`] |
+//! | `ureq` | [`UreqTransport`] | |
+//! | `hyper` | | [`HyperTransport`] |
+//!
+//! [`MockTransport`] is always available: it answers with canned responses, to test code using the
+//! client without network access.
+//!
+//! ```rust
+//! use zotero_api::{MockTransport, Zotero, ZoteroApi, ZoteroApiExecutor};
+//!
+//! let transport = MockTransport::new();
+//! transport.push_json(200, &serde_json::json!({"name": "Thesis"}));
+//! let z = Zotero::set_user("123456789", "bZARysJ579K5SdmYuaAJ").with_transport(transport.clone());
+//! let collection: serde_json::Value = z.get_collection("TYQDGEZR", None).execute(&z).unwrap();
+//! assert_eq!(collection["name"], "Thesis");
+//! assert_eq!(transport.take_requests()[0].uri().path(), "/users/123456789/collections/TYQDGEZR");
+//! ```
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{Request, Response};
+
+use crate::ZoteroApiError;
+
+mod mock;
+pub use mock::MockTransport;
+
+#[cfg(feature = "reqwest")]
+mod reqwest_impl;
+#[cfg(feature = "reqwest")]
+pub use reqwest_impl::{AsyncReqwestTransport, ReqwestTransport};
+
+#[cfg(feature = "ureq")]
+mod ureq_impl;
+#[cfg(feature = "ureq")]
+pub use ureq_impl::UreqTransport;
+
+#[cfg(feature = "hyper")]
+mod hyper_impl;
+#[cfg(feature = "hyper")]
+pub use hyper_impl::HyperTransport;
+
+/// A blocking HTTP client. Error statuses are returned as responses: only failures to send the request
+/// or to read the response are errors.
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, request: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError>;
+}
+
+/// An async HTTP client, see [`HttpTransport`].
+#[async_trait]
+pub trait AsyncHttpTransport: Send + Sync {
+    async fn send(&self, request: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError>;
+}
+
+/// Used when the crate is built without any transport feature and no transport is set on the client.
+struct NoTransport;
+
+impl NoTransport {
+    fn error() -> ZoteroApiError {
+        ZoteroApiError::RequestError(
+            "no HTTP transport: enable the reqwest, ureq or hyper feature, or set one on the client"
+                .to_string(),
+        )
+    }
+}
+
+impl HttpTransport for NoTransport {
+    fn send(&self, _: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+        Err(NoTransport::error())
+    }
+}
+
+#[async_trait]
+impl AsyncHttpTransport for NoTransport {
+    async fn send(&self, _: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+        Err(NoTransport::error())
+    }
+}
+
+/// The blocking transport of new clients: reqwest, else ureq.
+#[allow(unreachable_code)]
+pub(crate) fn default_transport() -> Arc<dyn HttpTransport> {
+    #[cfg(feature = "reqwest")]
+    return Arc::new(ReqwestTransport::default());
+    #[cfg(feature = "ureq")]
+    return Arc::new(UreqTransport::default());
+    Arc::new(NoTransport)
+}
+
+/// The async transport of new clients: reqwest, else hyper.
+#[allow(unreachable_code)]
+pub(crate) fn default_async_transport() -> Arc<dyn AsyncHttpTransport> {
+    #[cfg(feature = "reqwest")]
+    return Arc::new(AsyncReqwestTransport::default());
+    #[cfg(feature = "hyper")]
+    return Arc::new(HyperTransport::default());
+    Arc::new(NoTransport)
+}
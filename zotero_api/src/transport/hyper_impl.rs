@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{Request, Response};
+use hyper::client::HttpConnector;
+use hyper::{Body, Client};
+use hyper_tls::HttpsConnector;
+
+use super::AsyncHttpTransport;
+use crate::ZoteroApiError;
+
+/// Async transport using a `hyper` client over TLS. It needs a tokio runtime.
+#[derive(Debug)]
+pub struct HyperTransport {
+    client: Client<HttpsConnector<HttpConnector>>,
+}
+
+impl Default for HyperTransport {
+    fn default() -> HyperTransport {
+        HyperTransport {
+            client: Client::builder().build(HttpsConnector::new()),
+        }
+    }
+}
+
+impl From<Client<HttpsConnector<HttpConnector>>> for HyperTransport {
+    fn from(client: Client<HttpsConnector<HttpConnector>>) -> HyperTransport {
+        HyperTransport { client }
+    }
+}
+
+#[async_trait]
+impl AsyncHttpTransport for HyperTransport {
+    async fn send(&self, request: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+        let res = self
+            .client
+            .request(request.map(Body::from))
+            .await
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))?;
+        let (parts, body) = res.into_parts();
+        let body = hyper::body::to_bytes(body)
+            .await
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))?;
+        Ok(Response::from_parts(parts, body))
+    }
+}
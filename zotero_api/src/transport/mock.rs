@@ -0,0 +1,82 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{Request, Response};
+use serde::Serialize;
+
+use super::{AsyncHttpTransport, HttpTransport};
+use crate::ZoteroApiError;
+
+/// A transport answering with queued responses, in order, and recording the requests it receives.
+///
+/// Clones share their queue and records: keep a clone to inspect the requests sent by a client.
+/// A request sent when the queue is empty fails with a [`ZoteroApiError::RequestError`].
+#[derive(Clone, Debug, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    responses: VecDeque<Response<Bytes>>,
+    requests: Vec<Request<Bytes>>,
+}
+
+impl MockTransport {
+    pub fn new() -> MockTransport {
+        MockTransport::default()
+    }
+
+    /// Queue a response, with its headers such as `Last-Modified-Version` or `Link`.
+    pub fn push_response(&self, response: Response<Bytes>) {
+        self.state.lock().unwrap().responses.push_back(response);
+    }
+
+    /// Queue a response with a JSON body.
+    pub fn push_json<T: Serialize + ?Sized>(&self, status: u16, body: &T) {
+        let response = Response::builder()
+            .status(status)
+            .header("Content-Type", "application/json")
+            .body(Bytes::from(serde_json::to_vec(body).unwrap()))
+            .unwrap();
+        self.push_response(response);
+    }
+
+    /// Number of queued responses not sent yet.
+    pub fn pending(&self) -> usize {
+        self.state.lock().unwrap().responses.len()
+    }
+
+    /// The requests received so far, in order. They are removed from the records.
+    pub fn take_requests(&self) -> Vec<Request<Bytes>> {
+        std::mem::take(&mut self.state.lock().unwrap().requests)
+    }
+
+    fn respond(&self, request: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+        let mut state = self.state.lock().unwrap();
+        let response = state.responses.pop_front().ok_or_else(|| {
+            ZoteroApiError::RequestError(format!(
+                "no mock response for {} {}",
+                request.method(),
+                request.uri()
+            ))
+        });
+        state.requests.push(request);
+        response
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+        self.respond(request)
+    }
+}
+
+#[async_trait]
+impl AsyncHttpTransport for MockTransport {
+    async fn send(&self, request: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+        self.respond(request)
+    }
+}
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{Request, Response};
+use once_cell::sync::OnceCell;
+
+use super::{AsyncHttpTransport, HttpTransport};
+use crate::ZoteroApiError;
+
+/// Blocking transport using `reqwest::blocking`.
+#[derive(Debug, Default)]
+pub struct ReqwestTransport {
+    // the blocking client runs its own runtime: it is only created when sending the first request, so
+    // that clients can be built, and dropped, within an async context
+    client: OnceCell<reqwest::blocking::Client>,
+}
+
+impl From<reqwest::blocking::Client> for ReqwestTransport {
+    fn from(client: reqwest::blocking::Client) -> ReqwestTransport {
+        ReqwestTransport {
+            client: OnceCell::with_value(client),
+        }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+        let request = request
+            .try_into()
+            .map_err(|err: reqwest::Error| ZoteroApiError::RequestCreationError(err.to_string()))?;
+        let res = self
+            .client
+            .get_or_init(reqwest::blocking::Client::new)
+            .execute(request)
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))?;
+
+        let mut response = Response::builder().status(res.status());
+        *response.headers_mut().unwrap() = res.headers().clone();
+        let body = res
+            .bytes()
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))?;
+        response
+            .body(body)
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))
+    }
+}
+
+/// Async transport using `reqwest`.
+#[derive(Debug, Default)]
+pub struct AsyncReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl From<reqwest::Client> for AsyncReqwestTransport {
+    fn from(client: reqwest::Client) -> AsyncReqwestTransport {
+        AsyncReqwestTransport { client }
+    }
+}
+
+#[async_trait]
+impl AsyncHttpTransport for AsyncReqwestTransport {
+    async fn send(&self, request: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+        let request = request
+            .try_into()
+            .map_err(|err: reqwest::Error| ZoteroApiError::RequestCreationError(err.to_string()))?;
+        let res = self
+            .client
+            .execute(request)
+            .await
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))?;
+
+        let mut response = Response::builder().status(res.status());
+        *response.headers_mut().unwrap() = res.headers().clone();
+        let body = res
+            .bytes()
+            .await
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))?;
+        response
+            .body(body)
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))
+    }
+}
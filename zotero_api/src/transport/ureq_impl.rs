@@ -0,0 +1,61 @@
+use std::io::Read;
+
+use bytes::Bytes;
+use http::{Request, Response};
+
+use super::HttpTransport;
+use crate::ZoteroApiError;
+
+/// Blocking transport using `ureq`, lighter than reqwest for small binaries.
+#[derive(Debug)]
+pub struct UreqTransport {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqTransport {
+    fn default() -> UreqTransport {
+        UreqTransport {
+            agent: ureq::Agent::new(),
+        }
+    }
+}
+
+impl From<ureq::Agent> for UreqTransport {
+    fn from(agent: ureq::Agent) -> UreqTransport {
+        UreqTransport { agent }
+    }
+}
+
+impl HttpTransport for UreqTransport {
+    fn send(&self, request: Request<Bytes>) -> Result<Response<Bytes>, ZoteroApiError> {
+        let mut req = self
+            .agent
+            .request(request.method().as_str(), &request.uri().to_string());
+        for (name, value) in request.headers() {
+            let value = value
+                .to_str()
+                .map_err(|err| ZoteroApiError::RequestCreationError(err.to_string()))?;
+            req = req.set(name.as_str(), value);
+        }
+        let res = match req.send_bytes(request.body()) {
+            Ok(res) => res,
+            // error statuses are handled by the executors, like other responses
+            Err(ureq::Error::Status(_, res)) => res,
+            Err(err) => return Err(ZoteroApiError::RequestError(err.to_string())),
+        };
+
+        let mut response = Response::builder().status(res.status());
+        for name in res.headers_names() {
+            for value in res.all(&name) {
+                response = response.header(name.as_str(), value);
+            }
+        }
+        let mut body = Vec::new();
+        res.into_reader()
+            .read_to_end(&mut body)
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))?;
+        response
+            .body(Bytes::from(body))
+            .map_err(|err| ZoteroApiError::RequestError(err.to_string()))
+    }
+}
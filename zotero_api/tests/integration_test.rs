@@ -1,6 +1,11 @@
-use zotero_api::{Zotero, ZoteroApi};
-#[allow(clippy::assertions_on_constants)]
-use zotero_data::item::{BookData, BookDataBuilder};
+// lints raised by the original tests, kept as they were written
+#![allow(clippy::assertions_on_constants, clippy::bool_assert_comparison)]
+use zotero_api::{
+    MockTransport, Zotero, ZoteroApi, ZoteroApiError, ZoteroApiExecutor, ZoteroApiWrite,
+};
+use zotero_data::collection::Collection;
+use zotero_data::item::{BookData, BookDataBuilder, Item, ItemType};
+use zotero_data::shared_fields::Tag;
 
 #[cfg(test)]
 mod tests {
@@ -25,6 +30,27 @@ mod tests {
         assert!(true)
     }
 
+    #[test]
+    fn owned_client_across_threads() {
+        fn assert_shareable<T: Send + Sync + 'static>(_: &T) {}
+
+        let z = std::sync::Arc::new(Zotero::set_user(
+            String::from("123456789"),
+            String::from("abcdefgh"),
+        ));
+        assert_shareable(&z);
+
+        let key = String::from("ABREZSE");
+        let handle = {
+            let z = z.clone();
+            std::thread::spawn(move || z.get_item(&key, None).uri().to_string())
+        };
+        assert_eq!(
+            handle.join().unwrap(),
+            "https://api.zotero.org/users/123456789/items/ABREZSE"
+        );
+    }
+
     #[test]
     fn get_operation_for_user() {
         let z = Zotero::set_user("123456789", "abcdefgh");
@@ -45,25 +71,42 @@ mod tests {
         )
     }
 
+    #[test]
+    fn get_annotations() {
+        let z = Zotero::set_user("123456789", "abcdefgh");
+        assert_eq!(
+            z.get_annotations("QGKIJ5HT", None).uri(),
+            "https://api.zotero.org/users/123456789/items/QGKIJ5HT/children?itemType=annotation"
+        );
+        assert_eq!(
+            z.get_annotations("QGKIJ5HT", "limit=10").uri(),
+            "https://api.zotero.org/users/123456789/items/QGKIJ5HT/children?itemType=annotation&limit=10"
+        );
+    }
+
     #[test]
     fn delete_operation_for_user() {
         let z = Zotero::set_user("123456789", "abcdefgh");
         let req = z.delete_item("ABREZSE", "2050");
         assert_eq!(req.method(), "DELETE");
+        assert_eq!(
+            req.uri(),
+            "https://api.zotero.org/users/123456789/items/ABREZSE"
+        );
         assert_eq!(req.headers().contains_key("Authorization"), true);
     }
 
     #[test]
     fn delete_operation_for_group_no_api_key() {
         let z = Zotero::set_group("123456789", None);
-        let req = z.delete_item("ABREZSE", "2050");
-        assert_eq!(req.method(), "DELETE");
-        assert_eq!(req.headers().contains_key("Authorization"), false);
+        assert!(z.assume_write_access().is_err());
     }
 
     #[test]
     fn delete_operation_for_group() {
-        let z = Zotero::set_group("123456789", "abcdefgh");
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
         let req = z.delete_item("ABREZSE", "2050");
         assert_eq!(req.method(), "DELETE");
         assert_eq!(req.headers().contains_key("Authorization"), true);
@@ -71,7 +114,9 @@ mod tests {
 
     #[test]
     fn delete_collection_for_group() {
-        let z = Zotero::set_group("123456789", "abcdefgh");
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
         let req = z.delete_collection("ABREZSE", "2050");
         assert_eq!(req.method(), "DELETE");
         assert_eq!(req.headers().contains_key("Authorization"), true);
@@ -88,22 +133,97 @@ mod tests {
     #[test]
     fn delete_items_for_user() {
         let z = Zotero::set_user("123456789", "abcdefgh");
-        let req = z.delete_items(vec!["ABREZSE"], "2050");
+        let batches = z.delete_items(&["ABREZSE", "PJTUB2WE"], "2050");
+        assert_eq!(batches.len(), 1);
+        let req = &batches[0].request;
         assert_eq!(req.method(), "DELETE");
+        assert_eq!(
+            req.uri(),
+            "https://api.zotero.org/users/123456789/items?itemKey=ABREZSE,PJTUB2WE"
+        );
+        assert_eq!(req.headers()["If-Unmodified-Since-Version"], "2050");
         assert_eq!(req.headers().contains_key("Authorization"), true);
     }
 
     #[test]
     fn delete_items_for_group() {
-        let z = Zotero::set_group("123456789", "abcdefgh");
-        let req = z.delete_items(vec!["ABREZSE"], "2050");
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
+        let batches = z.delete_items(&["ABREZSE", "PJTUB2WE"], "2050");
+        assert_eq!(batches.len(), 1);
+        let req = &batches[0].request;
         assert_eq!(req.method(), "DELETE");
+        assert_eq!(
+            req.uri(),
+            "https://api.zotero.org/groups/123456789/items?itemKey=ABREZSE,PJTUB2WE"
+        );
+        assert_eq!(req.headers()["If-Unmodified-Since-Version"], "2050");
         assert_eq!(req.headers().contains_key("Authorization"), true);
     }
 
+    #[test]
+    fn trash_and_restore_item() {
+        let z = Zotero::set_user("123456789", "abcdefgh");
+        let req = z.trash_item("ABREZSE", "2050");
+        assert_eq!(req.method(), "PATCH");
+        assert_eq!(
+            req.uri(),
+            "https://api.zotero.org/users/123456789/items/ABREZSE"
+        );
+        assert_eq!(req.headers()["If-Unmodified-Since-Version"], "2050");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(req.body()).unwrap(),
+            serde_json::json!({"deleted": 1})
+        );
+
+        let req = z.restore_item("ABREZSE", "2051");
+        assert_eq!(req.headers()["If-Unmodified-Since-Version"], "2051");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(req.body()).unwrap(),
+            serde_json::json!({"deleted": 0})
+        );
+    }
+
+    #[test]
+    fn multi_key_batches() {
+        let z = Zotero::set_user("123456789", "abcdefgh");
+        let keys: Vec<String> = (0..120).map(|i| format!("KEY{:05}", i)).collect();
+
+        let batches = z.get_items_by_keys(&keys, "format=json");
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0].keys.len(), 50);
+        assert_eq!(batches[2].keys, keys[100..]);
+        assert_eq!(batches[0].request.method(), "GET");
+        assert_eq!(
+            batches[2].request.uri().query().unwrap(),
+            format!("itemKey={}&limit=50&format=json", keys[100..].join(","))
+        );
+
+        let batches = z.delete_collections(&keys[..60], "2050");
+        assert_eq!(batches.len(), 2);
+        assert_eq!(
+            batches[1].request.uri().path(),
+            "/users/123456789/collections"
+        );
+        assert_eq!(
+            batches[1].request.uri().query().unwrap(),
+            format!("collectionKey={}", keys[50..60].join(","))
+        );
+
+        let batches = z.delete_searches(&["HHF7BB4C"], "2050");
+        assert_eq!(
+            batches[0].request.uri(),
+            "https://api.zotero.org/users/123456789/searches?searchKey=HHF7BB4C"
+        );
+        assert!(z.delete_items::<&str>(&[], "2050").is_empty());
+    }
+
     #[test]
     fn delete_tag_for_group() {
-        let z = Zotero::set_group("123456789", "abcdefgh");
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
         let req = z.delete_tag("ABREZSE", "2050");
         assert_eq!(req.method(), "DELETE");
         assert_eq!(req.headers().contains_key("Authorization"), true);
@@ -119,7 +239,9 @@ mod tests {
 
     #[test]
     fn delete_tags_for_group() {
-        let z = Zotero::set_group("123456789", "abcdefgh");
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
         let req = z.delete_tags(vec!["ABREZSE"], "2050");
         assert_eq!(req.method(), "DELETE");
         assert_eq!(req.headers().contains_key("Authorization"), true);
@@ -143,9 +265,478 @@ mod tests {
 
     #[test]
     fn delete_search_for_group() {
-        let z = Zotero::set_group("123456789", "abcdefgh");
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
         let req = z.delete_search("ABREZSE", "2050");
         assert_eq!(req.method(), "DELETE");
         assert_eq!(req.headers().contains_key("Authorization"), true);
     }
+
+    fn item(key: &str, library: &str, relations: serde_json::Value) -> Item {
+        serde_json::from_value(serde_json::json!({
+            "key": key,
+            "version": 12,
+            "library": {
+                "type": library,
+                "id": 123456789,
+                "name": "john.doe",
+                "links": {"alternate": {"href": "https://www.zotero.org/john.doe", "type": "text/html"}}
+            },
+            "links": {"alternate": {"href": "https://www.zotero.org/john.doe/items/A", "type": "text/html"}},
+            "meta": {},
+            "data": {"key": key, "version": 12, "itemType": "book", "title": "Lorem", "relations": relations}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn link_and_unlink_items() {
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
+        let first = item(
+            "AAAAAAAA",
+            "group",
+            serde_json::json!({"owl:sameAs": "http://zotero.org/users/1/items/ZZZZZZZZ"}),
+        );
+        let second = item("BBBBBBBB", "group", serde_json::json!({}));
+
+        let [first_req, second_req] = z.link_items(&first, &second).unwrap();
+        assert_eq!(first_req.method(), "PATCH");
+        assert_eq!(
+            first_req.uri(),
+            "https://api.zotero.org/groups/123456789/items/AAAAAAAA"
+        );
+        assert_eq!(first_req.headers()["If-Unmodified-Since-Version"], "12");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(first_req.body()).unwrap(),
+            serde_json::json!({"relations": {
+                "dc:relation": "http://zotero.org/groups/123456789/items/BBBBBBBB",
+                "owl:sameAs": "http://zotero.org/users/1/items/ZZZZZZZZ"
+            }})
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(second_req.body()).unwrap(),
+            serde_json::json!({"relations": {
+                "dc:relation": "http://zotero.org/groups/123456789/items/AAAAAAAA"
+            }})
+        );
+
+        let linked = item(
+            "BBBBBBBB",
+            "group",
+            serde_json::json!({"dc:relation": [
+                "http://zotero.org/groups/123456789/items/AAAAAAAA",
+                "http://zotero.org/groups/123456789/items/CCCCCCCC"
+            ]}),
+        );
+        let [_, unlink_req] = z.unlink_items(&first, &linked).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(unlink_req.body()).unwrap(),
+            serde_json::json!({"relations": {
+                "dc:relation": "http://zotero.org/groups/123456789/items/CCCCCCCC"
+            }})
+        );
+
+        let unknown_library = item("CCCCCCCC", "unknown", serde_json::json!({}));
+        assert!(z.link_items(&first, &unknown_library).is_err());
+    }
+
+    #[test]
+    fn link_items_across_libraries() {
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
+        let group_item = item("AAAAAAAA", "group", serde_json::json!({}));
+        let mut user_item = item("BBBBBBBB", "user", serde_json::json!({}));
+        user_item.library.id = 42;
+
+        let [group_req, user_req] = z.link_items(&group_item, &user_item).unwrap();
+        assert_eq!(
+            group_req.uri(),
+            "https://api.zotero.org/groups/123456789/items/AAAAAAAA"
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(group_req.body()).unwrap(),
+            serde_json::json!({"relations": {"dc:relation": "http://zotero.org/users/42/items/BBBBBBBB"}})
+        );
+        assert_eq!(
+            user_req.uri(),
+            "https://api.zotero.org/users/42/items/BBBBBBBB"
+        );
+        assert_eq!(user_req.headers()["Authorization"], "Bearer abcdefgh");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(user_req.body()).unwrap(),
+            serde_json::json!({"relations": {"dc:relation": "http://zotero.org/groups/123456789/items/AAAAAAAA"}})
+        );
+    }
+
+    #[test]
+    fn merge_items_plan() {
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
+        let history = Tag {
+            tag: "history".to_string(),
+        };
+        let mut master = item("AAAAAAAA", "group", serde_json::json!({}));
+        master.data = ItemType::Book(
+            BookDataBuilder::default()
+                .key("AAAAAAAA")
+                .title("Lorem")
+                .tags(vec![history.clone()])
+                .collections(vec!["CCCCCCCC".to_string()])
+                .build()
+                .unwrap(),
+        );
+        let mut other = item("BBBBBBBB", "group", serde_json::json!({}));
+        other.data = ItemType::Book(
+            BookDataBuilder::default()
+                .key("BBBBBBBB")
+                .title("Another title")
+                .publisher("Doe editions")
+                .tags(vec![
+                    history,
+                    Tag {
+                        tag: "art".to_string(),
+                    },
+                ])
+                .collections(vec!["DDDDDDDD".to_string()])
+                .build()
+                .unwrap(),
+        );
+        let note: Item = serde_json::from_value(serde_json::json!({
+            "key": "NNNNNNNN",
+            "version": 3,
+            "library": {
+                "type": "group",
+                "id": 123456789,
+                "name": "john.doe",
+                "links": {"alternate": {"href": "https://www.zotero.org/john.doe", "type": "text/html"}}
+            },
+            "links": {"alternate": {"href": "https://www.zotero.org/john.doe/items/N", "type": "text/html"}},
+            "meta": {},
+            "data": {
+                "key": "NNNNNNNN",
+                "version": 3,
+                "itemType": "note",
+                "parentItem": "BBBBBBBB",
+                "note": "<p>Lorem</p>",
+                "tags": [],
+                "relations": {},
+                "dateAdded": "2020-01-01T00:00:00Z",
+                "dateModified": "2020-01-01T00:00:00Z"
+            }
+        }))
+        .unwrap();
+
+        let plan = z.plan_merge_items(&master, &[other], &[note]).unwrap();
+        assert_eq!(plan.requests.len(), 3);
+        assert_eq!(
+            plan.moved_children,
+            vec![("NNNNNNNN".to_string(), "BBBBBBBB".to_string())]
+        );
+        assert_eq!(plan.trashed, vec!["BBBBBBBB"]);
+
+        let master_req = &plan.requests[0];
+        assert_eq!(master_req.method(), "PATCH");
+        assert_eq!(
+            master_req.uri(),
+            "https://api.zotero.org/groups/123456789/items/AAAAAAAA"
+        );
+        assert_eq!(master_req.headers()["If-Unmodified-Since-Version"], "12");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(master_req.body()).unwrap(),
+            serde_json::json!({
+                "publisher": "Doe editions",
+                "tags": [{"tag": "history"}, {"tag": "art"}],
+                "collections": ["CCCCCCCC", "DDDDDDDD"],
+                "relations": {"dc:replaces": "http://zotero.org/groups/123456789/items/BBBBBBBB"}
+            })
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(plan.requests[1].body()).unwrap(),
+            serde_json::json!({"parentItem": "AAAAAAAA"})
+        );
+        assert_eq!(
+            plan.requests[1].headers()["If-Unmodified-Since-Version"],
+            "3"
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(plan.requests[2].body()).unwrap(),
+            serde_json::json!({"deleted": 1})
+        );
+
+        let report = plan.to_string();
+        assert!(report.starts_with("Update AAAAAAAA:\n"));
+        assert!(report.contains("  publisher: \"\" -> \"Doe editions\"\n"));
+        assert!(report.contains("Move NNNNNNNN from BBBBBBBB to AAAAAAAA"));
+        assert!(report.contains("Move BBBBBBBB to the trash"));
+
+        assert!(z
+            .plan_merge_items(&master, std::slice::from_ref(&master), &[])
+            .is_err());
+        assert!(z.plan_merge_items(&master, &[], &[]).is_err());
+    }
+
+    #[test]
+    fn move_collection() {
+        let z = Zotero::set_user("123456789", "abcdefgh");
+        let collection: Collection = serde_json::from_value(serde_json::json!({
+            "key": "CCCCCCCC",
+            "version": 7,
+            "library": {
+                "type": "user",
+                "id": 123456789,
+                "name": "john.doe",
+                "links": {"alternate": {"href": "https://www.zotero.org/john.doe", "type": "text/html"}}
+            },
+            "links": {"alternate": {"href": "https://www.zotero.org/john.doe/collections/C", "type": "text/html"}},
+            "meta": {},
+            "data": {"key": "CCCCCCCC", "version": 7, "name": "Lorem", "parentCollection": false, "relations": {}}
+        }))
+        .unwrap();
+
+        let req = z.move_collection(&collection, "PPPPPPPP").unwrap();
+        assert_eq!(req.method(), "PATCH");
+        assert_eq!(
+            req.uri(),
+            "https://api.zotero.org/users/123456789/collections/CCCCCCCC"
+        );
+        assert_eq!(req.headers()["If-Unmodified-Since-Version"], "7");
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(req.body()).unwrap(),
+            serde_json::json!({"parentCollection": "PPPPPPPP"})
+        );
+
+        let req = z.move_collection(&collection, None).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(req.body()).unwrap(),
+            serde_json::json!({"parentCollection": false})
+        );
+        assert!(z.move_collection(&collection, "CCCCCCCC").is_err());
+    }
+
+    #[test]
+    fn collection_membership() {
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
+        let member = |collections: &[&str]| {
+            let mut member = item("MEMBER00", "group", serde_json::json!({}));
+            member.data = ItemType::Book(
+                BookDataBuilder::default()
+                    .key("MEMBER00")
+                    .collections(
+                        collections
+                            .iter()
+                            .map(|c| c.to_string())
+                            .collect::<Vec<String>>(),
+                    )
+                    .build()
+                    .unwrap(),
+            );
+            member
+        };
+        let mut items: Vec<Item> = (0..60)
+            .map(|i| item(&format!("ITEM{:04}", i), "group", serde_json::json!({})))
+            .collect();
+        items[0] = member(&["CCCCCCCC"]);
+
+        let requests = z.add_items_to_collection(&items, "CCCCCCCC");
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method(), "POST");
+        assert_eq!(
+            requests[0].uri(),
+            "https://api.zotero.org/groups/123456789/items"
+        );
+        let first_batch = serde_json::from_slice::<serde_json::Value>(requests[0].body()).unwrap();
+        assert_eq!(first_batch.as_array().unwrap().len(), 50);
+        assert_eq!(
+            first_batch[0],
+            serde_json::json!({"key": "ITEM0001", "version": 12, "collections": ["CCCCCCCC"]})
+        );
+        let second_batch = serde_json::from_slice::<serde_json::Value>(requests[1].body()).unwrap();
+        assert_eq!(second_batch.as_array().unwrap().len(), 9);
+
+        let requests = z.remove_items_from_collection(
+            &[member(&["CCCCCCCC", "DDDDDDDD"]), items[1].clone()],
+            "CCCCCCCC",
+        );
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(requests[0].body()).unwrap(),
+            serde_json::json!([{"key": "MEMBER00", "version": 12, "collections": ["DDDDDDDD"]}])
+        );
+    }
+
+    #[test]
+    fn unknown_item_type_membership() {
+        let z = Zotero::set_group("123456789", "abcdefgh")
+            .assume_write_access()
+            .unwrap();
+        let item: Item = serde_json::from_value(serde_json::json!({
+            "key": "UUUUUUUU",
+            "version": 4,
+            "library": {
+                "type": "group",
+                "id": 123456789,
+                "name": "john.doe",
+                "links": {"alternate": {"href": "https://www.zotero.org/john.doe", "type": "text/html"}}
+            },
+            "links": {"alternate": {"href": "https://www.zotero.org/john.doe/items/U", "type": "text/html"}},
+            "meta": {},
+            "data": {
+                "key": "UUUUUUUU",
+                "version": 4,
+                "itemType": "hologram",
+                "title": "Survey",
+                "tags": [],
+                "collections": ["OTHERCOL"],
+                "relations": {}
+            }
+        }))
+        .unwrap();
+
+        let requests = z.add_items_to_collection(&[item], "CCCCCCCC");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(requests[0].body()).unwrap(),
+            serde_json::json!([{"key": "UUUUUUUU", "version": 4, "collections": ["OTHERCOL", "CCCCCCCC"]}])
+        );
+    }
+
+    fn versioned_response(
+        status: u16,
+        version: usize,
+        body: serde_json::Value,
+    ) -> http::Response<bytes::Bytes> {
+        http::Response::builder()
+            .status(status)
+            .header("Last-Modified-Version", version)
+            .body(match body {
+                serde_json::Value::Null => bytes::Bytes::new(),
+                body => bytes::Bytes::from(body.to_string()),
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn mock_pagination() {
+        let transport = MockTransport::new();
+        transport.push_response(
+            http::Response::builder()
+                .header(
+                    "Link",
+                    "<https://api.zotero.org/users/123456789/items?start=2>; rel=\"next\", \
+                     <https://api.zotero.org/users/123456789/items?start=2>; rel=\"last\"",
+                )
+                .body(bytes::Bytes::from(
+                    r#"[{"key": "ITEM0001"}, {"key": "ITEM0002"}]"#,
+                ))
+                .unwrap(),
+        );
+        transport.push_json(200, &serde_json::json!([{"key": "ITEM0003"}]));
+        let z = Zotero::set_user("123456789", "abcdefgh").with_transport(transport.clone());
+
+        let items: Vec<serde_json::Value> = z.get_items(None).execute(&z).unwrap();
+        assert_eq!(
+            items.iter().map(|item| &item["key"]).collect::<Vec<_>>(),
+            vec!["ITEM0001", "ITEM0002", "ITEM0003"]
+        );
+        let requests = transport.take_requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].uri(),
+            "https://api.zotero.org/users/123456789/items?start=2"
+        );
+        assert_eq!(requests[1].headers()["Authorization"], "Bearer abcdefgh");
+    }
+
+    #[test]
+    fn mock_errors() {
+        let transport = MockTransport::new();
+        transport.push_json(403, "Forbidden");
+        transport.push_json(404, "Not found");
+        let z = Zotero::set_user("123456789", "abcdefgh").with_transport(transport.clone());
+
+        let result: Result<serde_json::Value, _> = z.get_item("ITEM0001", None).execute(&z);
+        assert!(matches!(
+            result,
+            Err(ZoteroApiError::AuthenticationError(_))
+        ));
+        let result: Result<serde_json::Value, _> = z.get_item("ITEM0001", None).execute(&z);
+        assert!(matches!(result, Err(ZoteroApiError::RequestError(err)) if err.starts_with("404")));
+        // no response left
+        let result: Result<serde_json::Value, _> = z.get_item("ITEM0001", None).execute(&z);
+        assert!(matches!(result, Err(ZoteroApiError::RequestError(_))));
+        assert_eq!(transport.take_requests().len(), 3);
+    }
+
+    #[test]
+    fn mock_empty_trash() {
+        let transport = MockTransport::new();
+        transport.push_response(versioned_response(
+            200,
+            10,
+            serde_json::json!({"TRASH002": 8, "TRASH001": 9}),
+        ));
+        transport.push_response(versioned_response(204, 11, serde_json::Value::Null));
+        let z = Zotero::set_user("123456789", "abcdefgh").with_transport(transport.clone());
+
+        let result = z.empty_trash().unwrap();
+        assert!(result.is_ok());
+        assert_eq!(result.succeeded, vec!["TRASH001", "TRASH002"]);
+        let requests = transport.take_requests();
+        assert_eq!(
+            requests[0].uri(),
+            "https://api.zotero.org/users/123456789/items/trash?format=versions"
+        );
+        assert_eq!(requests[1].method(), "DELETE");
+        assert_eq!(
+            requests[1].uri(),
+            "https://api.zotero.org/users/123456789/items?itemKey=TRASH001,TRASH002"
+        );
+        assert_eq!(requests[1].headers()["If-Unmodified-Since-Version"], "10");
+    }
+
+    #[test]
+    fn mock_check_write_access() {
+        let transport = MockTransport::new();
+        transport.push_json(
+            200,
+            &serde_json::json!({
+                "key": "abcdefgh",
+                "userID": 1,
+                "access": {"groups": {"456": {"library": true, "write": true}}}
+            }),
+        );
+        transport.push_json(
+            200,
+            &serde_json::json!({"key": "abcdefgh", "userID": 1, "access": {}}),
+        );
+        let z = Zotero::set_group("456", "abcdefgh").with_transport(transport.clone());
+
+        assert!(z.clone().check_write_access().is_ok());
+        assert!(z.check_write_access().is_err());
+        assert_eq!(
+            transport.take_requests()[0].uri(),
+            "https://api.zotero.org/keys/abcdefgh"
+        );
+    }
+
+    #[tokio::test]
+    async fn mock_async_transport() {
+        use zotero_api::ZoteroApiAsyncExecutor;
+
+        let transport = MockTransport::new();
+        transport.push_json(200, &serde_json::json!({"key": "ITEM0001"}));
+        let z = Zotero::set_user("123456789", "abcdefgh").with_async_transport(transport.clone());
+
+        let request = z.get_item("ITEM0001", None);
+        let item: serde_json::Value = ZoteroApiAsyncExecutor::execute(request, &z).await.unwrap();
+        assert_eq!(item["key"], "ITEM0001");
+        assert_eq!(transport.pending(), 0);
+    }
 }
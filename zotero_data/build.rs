@@ -0,0 +1,781 @@
+//! Generate item type structs, builders, base-field mappings, `ItemType` variants and `CreatorType` from the
+//! vendored Zotero schema in `schema/schema.json`.
+//!
+//! `schema/schema.json` is Zotero's schema (<https://github.com/zotero/zotero-schema>), as served by
+//! `https://api.zotero.org/schema`. Replacing it with a newer copy and rebuilding is enough to pick up
+//! new item types, fields and creator types: it must not be edited by hand.
+//!
+//! Annotations, attachments and notes are not described by the schema in enough detail and are hand-written
+//! in `src/item/item_data`. They are added to the generated code even when the schema doesn't list them.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+/// Item types with a hand-written data struct.
+const SPECIAL_ITEM_TYPES: [&str; 3] = ["annotation", "attachment", "note"];
+
+struct Field {
+    name: String,
+    base_field: Option<String>,
+}
+
+struct ItemTypeSchema {
+    name: String,
+    fields: Vec<Field>,
+    creator_types: Vec<String>,
+    description: String,
+}
+
+impl ItemTypeSchema {
+    fn variant(&self) -> String {
+        let mut chars = self.name.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+
+    fn struct_name(&self) -> String {
+        format!("{}Data", self.variant())
+    }
+
+    fn is_special(&self) -> bool {
+        SPECIAL_ITEM_TYPES.contains(&self.name.as_str())
+    }
+}
+
+fn snake_case(field: &str) -> String {
+    if field.chars().all(|c| c.is_ascii_uppercase()) {
+        return field.to_ascii_lowercase();
+    }
+    // A run of capitals is a single word, e.g. `archiveID` becomes `archive_id`.
+    let mut out = String::new();
+    let mut previous_upper = false;
+    for c in field.chars() {
+        if c.is_ascii_uppercase() {
+            if !previous_upper {
+                out.push('_');
+            }
+            previous_upper = true;
+            out.push(c.to_ascii_lowercase());
+        } else {
+            previous_upper = false;
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Rust field name of a Zotero field, e.g. `abstractNote` becomes `abstract_note` and `type` becomes `r#type`.
+fn rust_field_name(field: &str) -> String {
+    let name = snake_case(field);
+    match name.as_str() {
+        "type" | "abstract" | "final" | "override" | "box" | "move" | "ref" | "static" => {
+            format!("r#{}", name)
+        }
+        _ => name,
+    }
+}
+
+fn camel_case(field: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in field.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn creator_type_variant(creator_type: &str) -> String {
+    let mut chars = creator_type.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Every creator type used by an item type, sorted by name.
+fn all_creator_types(item_types: &[ItemTypeSchema]) -> Vec<String> {
+    let mut creator_types: Vec<String> = item_types
+        .iter()
+        .flat_map(|t| t.creator_types.iter().cloned())
+        .collect();
+    creator_types.sort();
+    creator_types.dedup();
+    creator_types
+}
+
+fn load_schema(schema_dir: &Path) -> (Vec<ItemTypeSchema>, Vec<String>) {
+    let schema: Value =
+        serde_json::from_str(&fs::read_to_string(schema_dir.join("schema.json")).unwrap())
+            .expect("schema.json is not valid JSON");
+    let descriptions: BTreeMap<String, String> =
+        serde_json::from_str(&fs::read_to_string(schema_dir.join("descriptions.json")).unwrap())
+            .expect("descriptions.json is not valid JSON");
+
+    let mut item_types: Vec<ItemTypeSchema> = schema["itemTypes"]
+        .as_array()
+        .expect("schema.json has no itemTypes")
+        .iter()
+        .map(|item_type| {
+            let name = item_type["itemType"].as_str().unwrap().to_string();
+            let fields = item_type["fields"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|f| Field {
+                    name: f["field"].as_str().unwrap().to_string(),
+                    base_field: f["baseField"].as_str().map(String::from),
+                })
+                .collect();
+            let mut creator_types: Vec<(bool, String)> = item_type["creatorTypes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|c| {
+                    (
+                        !c["primary"].as_bool().unwrap_or(false),
+                        c["creatorType"].as_str().unwrap().to_string(),
+                    )
+                })
+                .collect();
+            // the primary creator type comes first
+            creator_types.sort_by_key(|(not_primary, _)| *not_primary);
+            let description = descriptions.get(&name).cloned().unwrap_or_default();
+            ItemTypeSchema {
+                name,
+                fields,
+                creator_types: creator_types.into_iter().map(|(_, c)| c).collect(),
+                description,
+            }
+        })
+        .collect();
+
+    for special in SPECIAL_ITEM_TYPES {
+        if !item_types.iter().any(|t| t.name == special) {
+            item_types.push(ItemTypeSchema {
+                name: special.to_string(),
+                fields: Vec::new(),
+                creator_types: Vec::new(),
+                description: descriptions.get(special).cloned().unwrap_or_default(),
+            });
+        }
+    }
+
+    // regular item types first, sorted by name, then annotations, attachments and notes
+    item_types.sort_by_key(|t| (t.is_special(), t.variant()));
+
+    let date_fields = schema["meta"]["fields"]
+        .as_object()
+        .map(|fields| {
+            fields
+                .iter()
+                .filter(|(_, meta)| meta["type"] == "date")
+                .map(|(name, _)| name.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    (item_types, date_fields)
+}
+
+fn generate_item_data(item_types: &[ItemTypeSchema]) -> String {
+    let mut out = String::new();
+    writeln!(out, "use crate::item::Creator;").unwrap();
+    writeln!(
+        out,
+        "use crate::shared_fields::{{FieldError, ItemCommon, ItemFields, Tag, UnknownFields}};"
+    )
+    .unwrap();
+    writeln!(out, "use crate::ToJson;").unwrap();
+    writeln!(out, "use derive_builder::Builder;").unwrap();
+    writeln!(out, "use serde::{{Deserialize, Serialize}};").unwrap();
+    writeln!(out, "use crate::relations::Relations;").unwrap();
+    writeln!(out, "use zotero_derive::{{ItemCommon, ItemFields}};").unwrap();
+
+    for item_type in item_types.iter().filter(|t| !t.is_special()) {
+        let struct_name = item_type.struct_name();
+        writeln!(out).unwrap();
+        writeln!(out, "#[doc = {:?}]", item_type.description).unwrap();
+        writeln!(
+            out,
+            "#[derive(Deserialize, Serialize, Clone, Debug, Builder, ItemCommon, ItemFields)]"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "#[serde(rename_all(deserialize = \"camelCase\", serialize = \"camelCase\"), default)]"
+        )
+        .unwrap();
+        writeln!(out, "#[builder(setter(into), default)]").unwrap();
+        writeln!(out, "pub struct {} {{", struct_name).unwrap();
+        writeln!(
+            out,
+            "    #[serde(skip_serializing_if = \"String::is_empty\")]\n    pub key: String,"
+        )
+        .unwrap();
+        writeln!(out, "    #[builder(setter(skip))]\n    pub version: usize,").unwrap();
+        writeln!(
+            out,
+            "    #[builder(setter(skip))]\n    pub item_type: String,"
+        )
+        .unwrap();
+
+        for (i, field) in item_type.fields.iter().enumerate() {
+            let rust_name = rust_field_name(&field.name);
+            let rename = match camel_case(&snake_case(&field.name)) == field.name {
+                true => String::new(),
+                false => format!(", rename = {:?}", field.name),
+            };
+            if let Some(base_field) = &field.base_field {
+                writeln!(out, "    #[zotero(base = {:?})]", base_field).unwrap();
+            }
+            writeln!(
+                out,
+                "    #[serde(skip_serializing_if = \"String::is_empty\"{})]\n    pub {}: String,",
+                rename, rust_name
+            )
+            .unwrap();
+            // Zotero lists creators right after the title
+            if i == 0 {
+                writeln!(
+                    out,
+                    "    #[serde(skip_serializing_if = \"Vec::is_empty\")]\n    pub creators: Vec<Creator>,"
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(out, "    pub tags: Vec<Tag>,").unwrap();
+        writeln!(out, "    pub collections: Option<Vec<String>>,").unwrap();
+        writeln!(out, "    pub relations: Relations,").unwrap();
+        writeln!(
+            out,
+            "    /// Set when the item is in the trash.\n    #[serde(\n        skip_serializing_if = \"crate::shared_fields::deleted::is_false\",\n        with = \"crate::shared_fields::deleted\"\n    )]\n    pub deleted: bool,"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    #[serde(skip_serializing)]\n    pub date_added: String,"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    #[serde(skip_serializing)]\n    pub date_modified: String,"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    #[serde(flatten)]\n    #[builder(setter(skip))]\n    pub unknown_fields: UnknownFields,"
+        )
+        .unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out).unwrap();
+        writeln!(out, "impl Default for {} {{", struct_name).unwrap();
+        writeln!(out, "    fn default() -> {} {{", struct_name).unwrap();
+        writeln!(out, "        {} {{", struct_name).unwrap();
+        writeln!(out, "            key: String::default(),").unwrap();
+        writeln!(out, "            version: usize::default(),").unwrap();
+        writeln!(
+            out,
+            "            item_type: {:?}.to_string(),",
+            item_type.name
+        )
+        .unwrap();
+        for field in &item_type.fields {
+            writeln!(
+                out,
+                "            {}: String::default(),",
+                rust_field_name(&field.name)
+            )
+            .unwrap();
+        }
+        writeln!(out, "            creators: Vec::default(),").unwrap();
+        writeln!(out, "            tags: Vec::default(),").unwrap();
+        writeln!(out, "            collections: None,").unwrap();
+        writeln!(out, "            relations: Relations::default(),").unwrap();
+        writeln!(out, "            deleted: false,").unwrap();
+        writeln!(out, "            date_added: String::default(),").unwrap();
+        writeln!(out, "            date_modified: String::default(),").unwrap();
+        writeln!(out, "            unknown_fields: UnknownFields::default(),").unwrap();
+        writeln!(out, "        }}\n    }}\n}}").unwrap();
+
+        writeln!(out).unwrap();
+        writeln!(out, "impl ToJson for {} {{}}", struct_name).unwrap();
+    }
+    out
+}
+
+fn generate_item_type(item_types: &[ItemTypeSchema]) -> String {
+    let mut out = String::new();
+    writeln!(out, "#[derive(Debug, Clone, ItemCommon, ItemFields)]").unwrap();
+    writeln!(
+        out,
+        "/// An enum that holds structs used to deserialize zotero item data into rust structs."
+    )
+    .unwrap();
+    writeln!(out, "pub enum ItemType {{").unwrap();
+    for item_type in item_types {
+        writeln!(out, "    #[doc = {:?}]", item_type.description).unwrap();
+        writeln!(
+            out,
+            "    {}({}),",
+            item_type.variant(),
+            item_type.struct_name()
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "    /// An item type unknown to this crate, with its fields kept as raw JSON so that it can be written back unchanged."
+    )
+    .unwrap();
+    writeln!(out, "    Unknown(UnknownItemData),").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "impl ItemType {{").unwrap();
+    writeln!(out, "    /// An empty item of the given kind.").unwrap();
+    writeln!(out, "    pub fn new(kind: ItemTypeKind) -> ItemType {{").unwrap();
+    writeln!(out, "        match kind {{").unwrap();
+    for item_type in item_types {
+        writeln!(
+            out,
+            "            ItemTypeKind::{0} => ItemType::{0}({1}::default()),",
+            item_type.variant(),
+            item_type.struct_name()
+        )
+        .unwrap();
+    }
+    writeln!(out, "        }}\n    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// The kind of this item, without its data. Returns `None` for unknown item types."
+    )
+    .unwrap();
+    writeln!(out, "    pub fn kind(&self) -> Option<ItemTypeKind> {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for item_type in item_types {
+        writeln!(
+            out,
+            "            ItemType::{0}(_) => Some(ItemTypeKind::{0}),",
+            item_type.variant()
+        )
+        .unwrap();
+    }
+    writeln!(out, "            ItemType::Unknown(_) => None,").unwrap();
+    writeln!(out, "        }}\n    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// The relations of this item. Relations of unknown item types are read from the raw JSON."
+    )
+    .unwrap();
+    writeln!(out, "    pub fn relations(&self) -> Relations {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for item_type in item_types {
+        writeln!(
+            out,
+            "            ItemType::{}(d) => d.relations.clone(),",
+            item_type.variant()
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "            ItemType::Unknown(data) => data\n                .get(\"relations\")\n                .and_then(|relations| serde_json::from_value(relations.clone()).ok())\n                .unwrap_or_default(),"
+    )
+    .unwrap();
+    writeln!(out, "        }}\n    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// Whether this item is in the trash. The flag of unknown item types is read from the raw JSON."
+    )
+    .unwrap();
+    writeln!(out, "    pub fn is_deleted(&self) -> bool {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for item_type in item_types {
+        writeln!(
+            out,
+            "            ItemType::{}(d) => d.deleted,",
+            item_type.variant()
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "            ItemType::Unknown(data) => data\n                .get(\"deleted\")\n                .is_some_and(crate::shared_fields::deleted::is_set),"
+    )
+    .unwrap();
+    writeln!(out, "        }}\n    }}\n}}").unwrap();
+
+    // Item types are dispatched on `itemType` by hand so that unknown item types end up in `ItemType::Unknown`
+    // instead of failing the whole response.
+    writeln!(out).unwrap();
+    writeln!(out, "impl<'de> Deserialize<'de> for ItemType {{").unwrap();
+    writeln!(
+        out,
+        "    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {{"
+    )
+    .unwrap();
+    writeln!(out, "        use serde::de::Error;").unwrap();
+    writeln!(
+        out,
+        "        let value = serde_json::Value::deserialize(deserializer)?;"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        let item_type = match value.get(\"itemType\").and_then(serde_json::Value::as_str) {{"
+    )
+    .unwrap();
+    writeln!(out, "            Some(item_type) => item_type.to_string(),").unwrap();
+    writeln!(
+        out,
+        "            None => return Err(D::Error::missing_field(\"itemType\")),"
+    )
+    .unwrap();
+    writeln!(out, "        }};").unwrap();
+    writeln!(out, "        match item_type.as_str() {{").unwrap();
+    for item_type in item_types {
+        writeln!(
+            out,
+            "            {:?} => serde_json::from_value(value).map(ItemType::{}).map_err(D::Error::custom),",
+            item_type.name,
+            item_type.variant()
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "            _ => serde_json::from_value(value).map(ItemType::Unknown).map_err(D::Error::custom),"
+    )
+    .unwrap();
+    writeln!(out, "        }}\n    }}\n}}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "impl Serialize for ItemType {{").unwrap();
+    writeln!(
+        out,
+        "    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {{"
+    )
+    .unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for item_type in item_types {
+        writeln!(
+            out,
+            "            ItemType::{}(d) => d.serialize(serializer),",
+            item_type.variant()
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "            ItemType::Unknown(data) => data.serialize(serializer),"
+    )
+    .unwrap();
+    writeln!(out, "        }}\n    }}\n}}").unwrap();
+    out
+}
+
+fn generate_item_type_kind(item_types: &[ItemTypeSchema], date_fields: &[String]) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "/// The kind of a Zotero item, without any of its data."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]"
+    )
+    .unwrap();
+    writeln!(out, "#[serde(rename_all = \"camelCase\")]").unwrap();
+    writeln!(out, "pub enum ItemTypeKind {{").unwrap();
+    for item_type in item_types {
+        writeln!(out, "    {},", item_type.variant()).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "impl ItemTypeKind {{").unwrap();
+    writeln!(
+        out,
+        "    /// Every item kind, in the same order as the `ItemType` variants."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    pub const ALL: [ItemTypeKind; {}] = [",
+        item_types.len()
+    )
+    .unwrap();
+    for item_type in item_types {
+        writeln!(out, "        ItemTypeKind::{},", item_type.variant()).unwrap();
+    }
+    writeln!(out, "    ];").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "    /// The `itemType` value used by the Zotero API.").unwrap();
+    writeln!(out, "    pub fn as_str(&self) -> &'static str {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for item_type in item_types {
+        writeln!(
+            out,
+            "            ItemTypeKind::{} => {:?},",
+            item_type.variant(),
+            item_type.name
+        )
+        .unwrap();
+    }
+    writeln!(out, "        }}\n    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// Zotero names of the fields of this item kind, in schema order."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    pub fn fields(&self) -> &'static [&'static str] {{"
+    )
+    .unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for item_type in item_types {
+        let fields: Vec<String> = item_type
+            .fields
+            .iter()
+            .map(|f| format!("{:?}", f.name))
+            .collect();
+        writeln!(
+            out,
+            "            ItemTypeKind::{} => &[{}],",
+            item_type.variant(),
+            fields.join(", ")
+        )
+        .unwrap();
+    }
+    writeln!(out, "        }}\n    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// The base field a type-specific field is mapped to, e.g. `caseName` is mapped to `title` for cases."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    pub fn base_field(&self, field: &str) -> Option<&'static str> {{"
+    )
+    .unwrap();
+    writeln!(out, "        match (self, field) {{").unwrap();
+    for item_type in item_types {
+        for field in &item_type.fields {
+            if let Some(base_field) = &field.base_field {
+                writeln!(
+                    out,
+                    "            (ItemTypeKind::{}, {:?}) => Some({:?}),",
+                    item_type.variant(),
+                    field.name,
+                    base_field
+                )
+                .unwrap();
+            }
+        }
+    }
+    writeln!(out, "            _ => None,\n        }}\n    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// The field of this item kind holding a base field: either the base field itself or a type-specific field mapped to it."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    pub fn field_for_base(&self, base_field: &str) -> Option<&'static str> {{"
+    )
+    .unwrap();
+    writeln!(out, "        self.fields()").unwrap();
+    writeln!(out, "            .iter()").unwrap();
+    writeln!(
+        out,
+        "            .find(|f| **f == base_field || self.base_field(f) == Some(base_field))"
+    )
+    .unwrap();
+    writeln!(out, "            .copied()\n    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// Creator types Zotero accepts for this item kind. The first one is the primary creator type.\n    /// Attachments and notes do not have creators."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    pub fn creator_types(&self) -> &'static [CreatorType] {{"
+    )
+    .unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for item_type in item_types {
+        let creator_types: Vec<String> = item_type
+            .creator_types
+            .iter()
+            .map(|c| format!("CreatorType::{}", creator_type_variant(c)))
+            .collect();
+        writeln!(
+            out,
+            "            ItemTypeKind::{} => &[{}],",
+            item_type.variant(),
+            creator_types.join(", ")
+        )
+        .unwrap();
+    }
+    writeln!(out, "        }}\n    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// Check whether a field holds a date, either directly or through its base field."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    pub fn is_date_field(&self, field: &str) -> bool {{"
+    )
+    .unwrap();
+    let date_fields: Vec<String> = date_fields.iter().map(|f| format!("{:?}", f)).collect();
+    writeln!(
+        out,
+        "        const DATE_FIELDS: [&str; {}] = [{}];",
+        date_fields.len(),
+        date_fields.join(", ")
+    )
+    .unwrap();
+    writeln!(out, "        DATE_FIELDS.contains(&field)").unwrap();
+    writeln!(
+        out,
+        "            || self.base_field(field).is_some_and(|base| DATE_FIELDS.contains(&base))"
+    )
+    .unwrap();
+    writeln!(out, "    }}\n}}").unwrap();
+    out
+}
+
+fn generate_creator_type(item_types: &[ItemTypeSchema]) -> String {
+    let creator_types = all_creator_types(item_types);
+    let mut out = String::new();
+    writeln!(
+        out,
+        "/// The role a creator plays for an item, as defined by the Zotero schema.\n///\n/// Creator types unknown to this crate are kept in [`CreatorType::Other`] so that they survive a round-trip."
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]\npub enum CreatorType {{"
+    )
+    .unwrap();
+    for creator_type in &creator_types {
+        if creator_type == "author" {
+            writeln!(out, "    #[default]").unwrap();
+        }
+        writeln!(out, "    {},", creator_type_variant(creator_type)).unwrap();
+    }
+    writeln!(out, "    Other(String),\n}}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "impl CreatorType {{").unwrap();
+    writeln!(
+        out,
+        "    /// The `creatorType` value used by the Zotero API.\n    pub fn as_str(&self) -> &str {{"
+    )
+    .unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for creator_type in &creator_types {
+        writeln!(
+            out,
+            "            CreatorType::{} => {:?},",
+            creator_type_variant(creator_type),
+            creator_type
+        )
+        .unwrap();
+    }
+    writeln!(out, "            CreatorType::Other(s) => s,").unwrap();
+    writeln!(out, "        }}\n    }}\n}}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "impl FromStr for CreatorType {{").unwrap();
+    writeln!(out, "    type Err = std::convert::Infallible;\n").unwrap();
+    writeln!(
+        out,
+        "    fn from_str(s: &str) -> Result<Self, Self::Err> {{\n        Ok(match s {{"
+    )
+    .unwrap();
+    for creator_type in &creator_types {
+        writeln!(
+            out,
+            "            {:?} => CreatorType::{},",
+            creator_type,
+            creator_type_variant(creator_type)
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "            other => CreatorType::Other(other.to_string()),"
+    )
+    .unwrap();
+    writeln!(out, "        }})\n    }}\n}}").unwrap();
+    out
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let schema_dir = Path::new(&manifest_dir).join("schema");
+    println!("cargo:rerun-if-changed=schema");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let (item_types, date_fields) = load_schema(&schema_dir);
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+    fs::write(
+        out_dir.join("item_data.rs"),
+        generate_item_data(&item_types),
+    )
+    .unwrap();
+    fs::write(
+        out_dir.join("item_type.rs"),
+        generate_item_type(&item_types),
+    )
+    .unwrap();
+    fs::write(
+        out_dir.join("creator_type.rs"),
+        generate_creator_type(&item_types),
+    )
+    .unwrap();
+    fs::write(
+        out_dir.join("item_type_kind.rs"),
+        generate_item_type_kind(&item_types, &date_fields),
+    )
+    .unwrap();
+}
@@ -1,8 +1,14 @@
+use crate::relations::Relations;
 use derive_builder::Builder;
-use std::collections::HashMap;
+
+mod tree;
+
+pub use tree::{CollectionNode, CollectionTree, CollectionTreeError};
 
 use serde::{Deserialize, Serialize};
 
+use crate::shared_fields::UnknownFields;
+
 #[derive(Default, Deserialize, Serialize, Debug, Clone)]
 pub struct Library {
     r#type: String,
@@ -48,7 +54,20 @@ pub struct CollectionData {
     pub version: usize,
     pub name: String,
     pub parent_collection: StringOrBool,
-    pub relations: HashMap<String, String>,
+    pub relations: Relations,
+    #[serde(flatten)]
+    #[builder(setter(skip))]
+    pub unknown_fields: UnknownFields,
+}
+
+impl CollectionData {
+    /// Key of the parent collection, `None` for top-level collections.
+    pub fn parent_key(&self) -> Option<&str> {
+        match &self.parent_collection {
+            StringOrBool::String(key) if !key.is_empty() => Some(key),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Default, Deserialize, Serialize, Debug, Clone)]
@@ -83,3 +102,22 @@ impl Default for StringOrBool {
         StringOrBool::Bool(false)
     }
 }
+
+#[cfg(test)]
+mod test_collection {
+    use super::*;
+
+    #[test]
+    fn collection_data_unknown_fields() {
+        let input = r#"{"key": "TYPDZEZF", "version": 12, "name": "Lorem", "parentCollection": false, "relations": {}, "futureField": [1, 2]}"#;
+        let collection = serde_json::from_str::<CollectionData>(input).unwrap();
+        assert_eq!(
+            collection.unknown_fields["futureField"],
+            serde_json::json!([1, 2])
+        );
+
+        let output = serde_json::to_value(&collection).unwrap();
+        assert_eq!(output["futureField"], serde_json::json!([1, 2]));
+        assert_eq!(output["name"], "Lorem");
+    }
+}
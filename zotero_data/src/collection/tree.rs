@@ -0,0 +1,323 @@
+//! Collections arranged by parent, with lookups by path such as `"Thesis/Chapter 2/Sources"`.
+//!
+//! ```rust
+//! use zotero_data::collection::{Collection, CollectionTree};
+//!
+//! # let collections: Vec<Collection> = Vec::new();
+//! let tree = CollectionTree::from_collections(collections).unwrap();
+//! if let Some(sources) = tree.get_by_path("Thesis/Chapter 2/Sources") {
+//!     println!("{}: {} item(s)", sources.key(), sources.total_items());
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::collection::Collection;
+
+/// Error returned by [`CollectionTree::from_collections`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollectionTreeError {
+    /// The collection is its own ancestor.
+    Cycle(String),
+}
+
+impl fmt::Display for CollectionTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectionTreeError::Cycle(key) => {
+                write!(f, "collection {} is its own ancestor", key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CollectionTreeError {}
+
+/// A collection with its subcollections.
+#[derive(Clone, Debug)]
+pub struct CollectionNode {
+    pub collection: Collection,
+    pub children: Vec<CollectionNode>,
+}
+
+impl CollectionNode {
+    pub fn key(&self) -> &str {
+        &self.collection.key
+    }
+
+    pub fn name(&self) -> &str {
+        &self.collection.data.name
+    }
+
+    /// Number of items directly in this collection, from its `meta`.
+    pub fn num_items(&self) -> usize {
+        self.collection.meta.num_items.unwrap_or_default()
+    }
+
+    /// Number of items in this collection and its subcollections. An item belonging to several of them is
+    /// counted once per collection.
+    pub fn total_items(&self) -> usize {
+        self.iter().map(|(_, node)| node.num_items()).sum()
+    }
+
+    /// Number of subcollections at any depth.
+    pub fn total_collections(&self) -> usize {
+        self.iter().count() - 1
+    }
+
+    /// The collection and its subcollections, depth-first, with their depth relative to this collection.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &CollectionNode)> {
+        let mut stack = vec![(0, self)];
+        std::iter::from_fn(move || {
+            let (depth, node) = stack.pop()?;
+            stack.extend(node.children.iter().rev().map(|child| (depth + 1, child)));
+            Some((depth, node))
+        })
+    }
+
+    /// Keys of the collection and its subcollections, e.g. to list the items of the whole subtree.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|(_, node)| node.key())
+    }
+
+    /// The subcollection named `name`.
+    pub fn child(&self, name: &str) -> Option<&CollectionNode> {
+        self.children.iter().find(|child| child.name() == name)
+    }
+
+    fn find(&self, key: &str) -> Option<&CollectionNode> {
+        match self.key() == key {
+            true => Some(self),
+            false => self.children.iter().find_map(|child| child.find(key)),
+        }
+    }
+}
+
+/// Collections arranged by parent.
+#[derive(Clone, Debug, Default)]
+pub struct CollectionTree {
+    pub roots: Vec<CollectionNode>,
+}
+
+impl CollectionTree {
+    /// Arrange downloaded collections by their `parentCollection`. Collections keep their order among their
+    /// siblings, and collections whose parent is not in `collections` are kept as roots.
+    pub fn from_collections(
+        collections: Vec<Collection>,
+    ) -> Result<CollectionTree, CollectionTreeError> {
+        let keys: HashSet<&str> = collections.iter().map(|c| c.key.as_str()).collect();
+        let has_parent: Vec<bool> = collections
+            .iter()
+            .map(|c| matches!(c.data.parent_key(), Some(parent) if keys.contains(parent)))
+            .collect();
+        let mut children: HashMap<String, Vec<Collection>> = HashMap::new();
+        let mut roots = Vec::new();
+        for (collection, has_parent) in collections.into_iter().zip(has_parent) {
+            match (has_parent, collection.data.parent_key()) {
+                (true, Some(parent)) => children
+                    .entry(parent.to_string())
+                    .or_default()
+                    .push(collection),
+                _ => roots.push(collection),
+            }
+        }
+
+        fn node(
+            collection: Collection,
+            children: &mut HashMap<String, Vec<Collection>>,
+        ) -> CollectionNode {
+            let collection_children = children.remove(&collection.key).unwrap_or_default();
+            CollectionNode {
+                children: collection_children
+                    .into_iter()
+                    .map(|child| node(child, children))
+                    .collect(),
+                collection,
+            }
+        }
+        let roots = roots
+            .into_iter()
+            .map(|collection| node(collection, &mut children))
+            .collect();
+        // collections that are their own ancestors are not reachable from a root
+        match children.values().flatten().map(|c| &c.key).min() {
+            Some(key) => Err(CollectionTreeError::Cycle(key.clone())),
+            None => Ok(CollectionTree { roots }),
+        }
+    }
+
+    /// Every collection, depth-first, with its depth: 0 for top-level collections, 1 for their subcollections
+    /// and so on.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &CollectionNode)> {
+        self.roots.iter().flat_map(CollectionNode::iter)
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&CollectionNode> {
+        self.roots.iter().find_map(|root| root.find(key))
+    }
+
+    /// The collection at `path`, a list of collection names separated by `/`, starting from a top-level
+    /// collection. When siblings share a name, the first one is used.
+    pub fn get_by_path(&self, path: &str) -> Option<&CollectionNode> {
+        match self.missing_path(path) {
+            (node, missing) if missing.is_empty() => node,
+            _ => None,
+        }
+    }
+
+    /// The path of a collection, its name and the names of its ancestors separated by `/`.
+    pub fn path(&self, key: &str) -> Option<String> {
+        fn search<'a>(node: &'a CollectionNode, key: &str, names: &mut Vec<&'a str>) -> bool {
+            names.push(node.name());
+            if node.key() == key || node.children.iter().any(|c| search(c, key, names)) {
+                return true;
+            }
+            names.pop();
+            false
+        }
+        let mut names = Vec::new();
+        self.roots
+            .iter()
+            .any(|root| search(root, key, &mut names))
+            .then(|| names.join("/"))
+    }
+
+    /// The deepest existing collection along `path`, and the names of the collections missing below it.
+    /// The collection is `None` when the top-level collection itself is missing.
+    pub fn missing_path<'p>(&self, path: &'p str) -> (Option<&CollectionNode>, Vec<&'p str>) {
+        let mut names = path_names(path);
+        let mut node: Option<&CollectionNode> = None;
+        while let Some(name) = names.first() {
+            let next = match node {
+                None => self.roots.iter().find(|root| root.name() == *name),
+                Some(node) => node.child(name),
+            };
+            match next {
+                Some(next) => {
+                    node = Some(next);
+                    names.remove(0);
+                }
+                None => break,
+            }
+        }
+        (node, names)
+    }
+}
+
+/// The collection names of a path, ignoring empty names.
+fn path_names(path: &str) -> Vec<&str> {
+    path.split('/')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod test_tree {
+    use super::*;
+
+    fn collection(key: &str, name: &str, parent: Option<&str>, num_items: usize) -> Collection {
+        serde_json::from_value(serde_json::json!({
+            "key": key,
+            "version": 1,
+            "library": {
+                "type": "user",
+                "id": 1,
+                "name": "john.doe",
+                "links": {"alternate": {"href": "https://www.zotero.org/john.doe", "type": "text/html"}}
+            },
+            "links": {"alternate": {"href": "https://www.zotero.org/john.doe/collections/A", "type": "text/html"}},
+            "meta": {"numCollections": 0, "numItems": num_items},
+            "data": {
+                "key": key,
+                "version": 1,
+                "name": name,
+                "parentCollection": parent.map_or(serde_json::json!(false), |p| serde_json::json!(p)),
+                "relations": {}
+            }
+        }))
+        .unwrap()
+    }
+
+    fn collections() -> Vec<Collection> {
+        vec![
+            collection("SOURCES1", "Sources", Some("CHAPTER2"), 4),
+            collection("THESIS01", "Thesis", None, 1),
+            collection("CHAPTER1", "Chapter 1", Some("THESIS01"), 2),
+            collection("CHAPTER2", "Chapter 2", Some("THESIS01"), 3),
+            collection("READING1", "Reading", None, 5),
+            collection("ORPHAN01", "Orphan", Some("MISSING1"), 0),
+        ]
+    }
+
+    #[test]
+    fn assemble() {
+        let tree = CollectionTree::from_collections(collections()).unwrap();
+        assert_eq!(
+            tree.iter()
+                .map(|(depth, node)| (depth, node.key()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "THESIS01"),
+                (1, "CHAPTER1"),
+                (1, "CHAPTER2"),
+                (2, "SOURCES1"),
+                (0, "READING1"),
+                (0, "ORPHAN01"),
+            ]
+        );
+        assert_eq!(tree.len(), 6);
+
+        let thesis = tree.get("THESIS01").unwrap();
+        assert_eq!(thesis.total_items(), 10);
+        assert_eq!(thesis.total_collections(), 3);
+        assert_eq!(
+            thesis.keys().collect::<Vec<_>>(),
+            vec!["THESIS01", "CHAPTER1", "CHAPTER2", "SOURCES1"]
+        );
+    }
+
+    #[test]
+    fn paths() {
+        let tree = CollectionTree::from_collections(collections()).unwrap();
+        assert_eq!(
+            tree.get_by_path("Thesis/Chapter 2/Sources").unwrap().key(),
+            "SOURCES1"
+        );
+        assert_eq!(
+            tree.get_by_path("/Thesis/ Chapter 1 /").unwrap().key(),
+            "CHAPTER1"
+        );
+        assert!(tree.get_by_path("Thesis/Chapter 3").is_none());
+        assert!(tree.get_by_path("").is_none());
+        assert_eq!(tree.path("SOURCES1").unwrap(), "Thesis/Chapter 2/Sources");
+        assert!(tree.path("UNKNOWN1").is_none());
+
+        let (node, missing) = tree.missing_path("Thesis/Chapter 2/Notes/Drafts");
+        assert_eq!(node.unwrap().key(), "CHAPTER2");
+        assert_eq!(missing, vec!["Notes", "Drafts"]);
+        let (node, missing) = tree.missing_path("Articles");
+        assert!(node.is_none());
+        assert_eq!(missing, vec!["Articles"]);
+    }
+
+    #[test]
+    fn cycle() {
+        let mut collections = collections();
+        collections.push(collection("LOOP0001", "Loop 1", Some("LOOP0002"), 0));
+        collections.push(collection("LOOP0002", "Loop 2", Some("LOOP0001"), 0));
+        assert_eq!(
+            CollectionTree::from_collections(collections).unwrap_err(),
+            CollectionTreeError::Cycle("LOOP0001".to_string())
+        );
+    }
+}
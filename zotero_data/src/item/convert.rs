@@ -0,0 +1,242 @@
+//! Conversion of an item to another item type, as done by the item type menu of Zotero.
+//!
+//! ```rust
+//! use zotero_data::item::{ItemType, ItemTypeKind, ReportDataBuilder};
+//! use zotero_data::shared_fields::ItemFields;
+//!
+//! let report = ItemType::Report(
+//!     ReportDataBuilder::default()
+//!         .title("Lorem")
+//!         .institution("Doe institute")
+//!         .build()
+//!         .unwrap(),
+//! );
+//!
+//! let (book, dropped) = report.convert_to(ItemTypeKind::Book);
+//! assert_eq!(book.get_field("publisher"), Some("Doe institute"));
+//! assert!(dropped.is_empty());
+//! ```
+
+use serde_json::Value;
+use std::fmt;
+
+use crate::item::{Creator, ItemType, ItemTypeKind};
+use crate::shared_fields::ItemFields;
+
+/// Something that could not be carried over by [`ItemType::convert_to`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum DroppedField {
+    /// A field or property that does not exist in the target item type, with its Zotero name and value.
+    Field { name: String, value: String },
+    /// A creator whose type is not valid for the target item type.
+    Creator(Creator),
+}
+
+impl fmt::Display for DroppedField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DroppedField::Field { name, value } => write!(f, "{}: {}", name, value),
+            DroppedField::Creator(creator) => {
+                write!(f, "{}: {}", creator.creator_type(), creator.full_name())
+            }
+        }
+    }
+}
+
+impl ItemType {
+    /// Convert the item to another item type.
+    ///
+    /// Fields are carried over by name, or through their base field: the `institution` of a report becomes the
+    /// `publisher` of a book. The key, version, tags, collections and relations are kept, as well as creators
+    /// whose type is valid for the target. Non-empty fields, other properties and creators that could not be
+    /// carried over are returned along with the converted item. Properties that are not text fields are reported
+    /// with their JSON value.
+    pub fn convert_to(&self, target: ItemTypeKind) -> (ItemType, Vec<DroppedField>) {
+        let mut dropped = Vec::new();
+        let source_kind = self.kind();
+        let text_fields: Vec<(&str, &str)> = self.fields().collect();
+
+        // everything that is neither a text field nor a creator (key, version, tags, collections, relations and
+        // fields unknown to this crate) is kept as is
+        let mut envelope = serde_json::Map::new();
+        if let Ok(Value::Object(source)) = serde_json::to_value(self) {
+            for (name, value) in source {
+                if name != "itemType"
+                    && name != "creators"
+                    && !text_fields.iter().any(|(n, _)| *n == name)
+                {
+                    envelope.insert(name, value);
+                }
+            }
+        }
+        // the envelope is laid over an empty item of the target type, which has every field the target requires
+        let mut target_fields = match serde_json::to_value(ItemType::new(target)) {
+            Ok(Value::Object(fields)) => fields,
+            _ => serde_json::Map::new(),
+        };
+        for (name, value) in &envelope {
+            target_fields.insert(name.clone(), value.clone());
+        }
+        target_fields.insert("itemType".to_string(), Value::from(target.as_str()));
+        let mut converted = serde_json::from_value::<ItemType>(Value::Object(target_fields))
+            .unwrap_or_else(|_| ItemType::new(target));
+        let kept = match serde_json::to_value(&converted) {
+            Ok(Value::Object(kept)) => kept,
+            _ => serde_json::Map::new(),
+        };
+        for (name, value) in envelope {
+            if kept.get(&name) != Some(&value) {
+                dropped.push(DroppedField::Field {
+                    value: match value {
+                        Value::String(value) => value,
+                        value => value.to_string(),
+                    },
+                    name,
+                });
+            }
+        }
+
+        for (name, value) in text_fields.into_iter().filter(|(_, v)| !v.is_empty()) {
+            let base = source_kind.and_then(|kind| kind.base_field(name));
+            let carried = converted.set_field(name, value).is_ok()
+                || base.is_some_and(|base| converted.set_field(base, value).is_ok());
+            if !carried {
+                dropped.push(DroppedField::Field {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                });
+            }
+        }
+
+        let (valid, invalid): (Vec<&Creator>, Vec<&Creator>) = self
+            .creators()
+            .iter()
+            .partition(|c| target.is_valid_creator_type(c.creator_type()));
+        match converted.creators_mut() {
+            Some(creators) => *creators = valid.into_iter().cloned().collect(),
+            None => dropped.extend(valid.into_iter().cloned().map(DroppedField::Creator)),
+        }
+        dropped.extend(invalid.into_iter().cloned().map(DroppedField::Creator));
+
+        (converted, dropped)
+    }
+}
+
+#[cfg(test)]
+mod test_convert {
+    use super::*;
+    use crate::item::{CreatorType, ReportDataBuilder, WebpageDataBuilder};
+    use crate::relations::RelationPredicate;
+    use crate::shared_fields::{ItemCommon, Tag};
+
+    #[test]
+    fn webpage_to_blog_post() {
+        let mut webpage = WebpageDataBuilder::default()
+            .title("Lorem")
+            .website_title("Ipsum")
+            .website_type("Personal website")
+            .date("2021-03-01")
+            .creators(vec![
+                Creator::two_field("author", "John", "Doe"),
+                Creator::two_field("translator", "Jane", "Doe"),
+            ])
+            .tags(vec![Tag {
+                tag: "history".to_string(),
+            }])
+            .build()
+            .unwrap();
+        webpage.key = "ABCD2345".to_string();
+        webpage.version = 12;
+        webpage.relations.add(
+            RelationPredicate::Relation,
+            "http://zotero.org/users/1/items/AAAAAAAA",
+        );
+
+        let (converted, dropped) = ItemType::Webpage(webpage).convert_to(ItemTypeKind::BlogPost);
+        let ItemType::BlogPost(blog_post) = &converted else {
+            panic!("expected a blog post, got {:?}", converted);
+        };
+        assert_eq!(blog_post.title, "Lorem");
+        assert_eq!(blog_post.blog_title, "Ipsum");
+        assert_eq!(blog_post.website_type, "Personal website");
+        assert_eq!(blog_post.date, "2021-03-01");
+        assert_eq!(blog_post.key, "ABCD2345");
+        assert_eq!(blog_post.version, 12);
+        assert!(converted.has_tag("history".to_string()));
+        assert_eq!(converted.relations().related().len(), 1);
+        assert_eq!(blog_post.creators.len(), 1);
+        assert_eq!(
+            dropped,
+            vec![DroppedField::Creator(Creator::two_field(
+                CreatorType::Translator,
+                "Jane",
+                "Doe"
+            ))]
+        );
+    }
+
+    #[test]
+    fn report_to_journal_article() {
+        let report = ItemType::Report(
+            ReportDataBuilder::default()
+                .title("Lorem")
+                .institution("Doe institute")
+                .report_number("42")
+                .pages("1-10")
+                .build()
+                .unwrap(),
+        );
+
+        let (converted, dropped) = report.convert_to(ItemTypeKind::JournalArticle);
+        assert_eq!(converted.kind(), Some(ItemTypeKind::JournalArticle));
+        assert_eq!(converted.get_field("pages"), Some("1-10"));
+        assert_eq!(
+            serde_json::to_value(&converted).unwrap()["itemType"],
+            "journalArticle"
+        );
+        assert!(dropped.contains(&DroppedField::Field {
+            name: "institution".to_string(),
+            value: "Doe institute".to_string()
+        }));
+        assert!(dropped.contains(&DroppedField::Field {
+            name: "reportNumber".to_string(),
+            value: "42".to_string()
+        }));
+    }
+
+    #[test]
+    fn book_to_attachment() {
+        let mut book = crate::item::BookDataBuilder::default()
+            .title("T")
+            .publisher("Doe editions")
+            .tags(vec![Tag {
+                tag: "x".to_string(),
+            }])
+            .collections(vec!["C1".to_string()])
+            .build()
+            .unwrap();
+        book.key = "ABCD2345".to_string();
+        book.version = 7;
+
+        let (converted, dropped) = ItemType::Book(book).convert_to(ItemTypeKind::Attachment);
+        let ItemType::Attachment(attachment) = &converted else {
+            panic!("expected an attachment, got {:?}", converted);
+        };
+        assert_eq!(attachment.title, "T");
+        assert_eq!(attachment.key, "ABCD2345");
+        assert_eq!(attachment.version, 7);
+        assert!(converted.has_tag("x".to_string()));
+        assert_eq!(converted.collections(), ["C1"]);
+        assert_eq!(
+            dropped,
+            vec![DroppedField::Field {
+                name: "publisher".to_string(),
+                value: "Doe editions".to_string()
+            }]
+        );
+
+        let (note, _) = converted.convert_to(ItemTypeKind::Note);
+        assert_eq!(note.key(), "ABCD2345");
+        assert_eq!(note.collections(), ["C1"]);
+    }
+}
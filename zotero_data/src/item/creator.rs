@@ -0,0 +1,281 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+include!(concat!(env!("OUT_DIR"), "/creator_type.rs"));
+
+impl fmt::Display for CreatorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for CreatorType {
+    fn from(s: &str) -> CreatorType {
+        let Ok(creator_type) = s.parse();
+        creator_type
+    }
+}
+
+impl From<String> for CreatorType {
+    fn from(s: String) -> CreatorType {
+        CreatorType::from(s.as_str())
+    }
+}
+
+impl Serialize for CreatorType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CreatorType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(CreatorType::from(s))
+    }
+}
+
+/// A creator of an item. Zotero stores creators either with two fields (`firstName` and `lastName`)
+/// or with a single `name` field, typically used for institutions.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum Creator {
+    TwoField {
+        #[serde(rename = "creatorType")]
+        creator_type: CreatorType,
+        #[serde(rename = "firstName", default)]
+        first_name: String,
+        #[serde(rename = "lastName")]
+        last_name: String,
+    },
+    SingleField {
+        #[serde(rename = "creatorType")]
+        creator_type: CreatorType,
+        name: String,
+    },
+}
+
+impl Creator {
+    /// Create a creator with separate first and last names.
+    pub fn two_field<T: Into<CreatorType>, S: Into<String>>(
+        creator_type: T,
+        first_name: S,
+        last_name: S,
+    ) -> Creator {
+        Creator::TwoField {
+            creator_type: creator_type.into(),
+            first_name: first_name.into(),
+            last_name: last_name.into(),
+        }
+    }
+
+    /// Create a creator stored in single-field mode, such as an institution.
+    pub fn single_field<T: Into<CreatorType>, S: Into<String>>(
+        creator_type: T,
+        name: S,
+    ) -> Creator {
+        Creator::SingleField {
+            creator_type: creator_type.into(),
+            name: name.into(),
+        }
+    }
+
+    pub fn creator_type(&self) -> &CreatorType {
+        match self {
+            Creator::TwoField { creator_type, .. } | Creator::SingleField { creator_type, .. } => {
+                creator_type
+            }
+        }
+    }
+
+    pub fn is_single_field(&self) -> bool {
+        matches!(self, Creator::SingleField { .. })
+    }
+
+    /// The last name of a two-field creator, or the whole name of a single-field creator.
+    pub fn last_name(&self) -> &str {
+        match self {
+            Creator::TwoField { last_name, .. } => last_name,
+            Creator::SingleField { name, .. } => name,
+        }
+    }
+
+    pub fn full_name(&self) -> String {
+        match self {
+            Creator::TwoField {
+                first_name,
+                last_name,
+                ..
+            } if first_name.is_empty() => last_name.to_string(),
+            Creator::TwoField {
+                first_name,
+                last_name,
+                ..
+            } => format!("{} {}", first_name, last_name),
+            Creator::SingleField { name, .. } => name.to_string(),
+        }
+    }
+
+    pub fn short_name(&self) -> String {
+        match self {
+            Creator::TwoField {
+                first_name,
+                last_name,
+                ..
+            } => match first_name.chars().next() {
+                Some(first_initial) => format!("{}. {}", first_initial, last_name),
+                None => last_name.to_string(),
+            },
+            Creator::SingleField { name, .. } => name.to_string(),
+        }
+    }
+}
+
+/// Error returned by [`CreatorBuilder::build`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CreatorBuilderError {
+    UninitializedField(&'static str),
+    ValidationError(String),
+}
+
+impl fmt::Display for CreatorBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CreatorBuilderError::UninitializedField(field) => {
+                write!(f, "`{}` must be initialized", field)
+            }
+            CreatorBuilderError::ValidationError(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for CreatorBuilderError {}
+
+/// Builder for [`Creator`]. Setting `name` builds a single-field creator, setting `first_name` and
+/// `last_name` builds a two-field creator.
+#[derive(Default, Clone, Debug)]
+pub struct CreatorBuilder {
+    creator_type: Option<CreatorType>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    name: Option<String>,
+}
+
+impl CreatorBuilder {
+    pub fn creator_type<V: Into<CreatorType>>(&mut self, value: V) -> &mut Self {
+        self.creator_type = Some(value.into());
+        self
+    }
+
+    pub fn first_name<V: Into<String>>(&mut self, value: V) -> &mut Self {
+        self.first_name = Some(value.into());
+        self
+    }
+
+    pub fn last_name<V: Into<String>>(&mut self, value: V) -> &mut Self {
+        self.last_name = Some(value.into());
+        self
+    }
+
+    pub fn name<V: Into<String>>(&mut self, value: V) -> &mut Self {
+        self.name = Some(value.into());
+        self
+    }
+
+    pub fn build(&self) -> Result<Creator, CreatorBuilderError> {
+        let creator_type = self
+            .creator_type
+            .clone()
+            .ok_or(CreatorBuilderError::UninitializedField("creator_type"))?;
+
+        match (&self.name, &self.first_name, &self.last_name) {
+            (Some(name), None, None) => Ok(Creator::SingleField {
+                creator_type,
+                name: name.clone(),
+            }),
+            (None, first_name, Some(last_name)) => Ok(Creator::TwoField {
+                creator_type,
+                first_name: first_name.clone().unwrap_or_default(),
+                last_name: last_name.clone(),
+            }),
+            (None, _, None) => Err(CreatorBuilderError::UninitializedField("last_name")),
+            (Some(_), _, _) => Err(CreatorBuilderError::ValidationError(
+                "`name` can not be combined with `first_name` or `last_name`".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_creator {
+    use super::*;
+
+    #[test]
+    fn two_field_deserialization() {
+        let input = r#"{"creatorType": "editor", "firstName": "John", "lastName": "Doe"}"#;
+        let result = serde_json::from_str::<Creator>(input).unwrap();
+        assert_eq!(
+            result,
+            Creator::two_field(CreatorType::Editor, "John", "Doe")
+        );
+    }
+
+    #[test]
+    fn single_field_deserialization() {
+        let input = r#"{"creatorType": "author", "name": "WHO"}"#;
+        let result = serde_json::from_str::<Creator>(input).unwrap();
+        assert_eq!(result, Creator::single_field(CreatorType::Author, "WHO"));
+        assert_eq!(result.full_name(), "WHO");
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({"creatorType": "author", "name": "WHO"})
+        );
+    }
+
+    #[test]
+    fn unknown_creator_type_round_trip() {
+        let input = r#"{"creatorType": "narrator", "name": "Jane"}"#;
+        let result = serde_json::from_str::<Creator>(input).unwrap();
+        assert_eq!(
+            result.creator_type(),
+            &CreatorType::Other("narrator".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(&result).unwrap()["creatorType"],
+            "narrator"
+        );
+    }
+
+    #[test]
+    fn creator_builder() {
+        let person = CreatorBuilder::default()
+            .creator_type("seriesEditor")
+            .first_name("John")
+            .last_name("Doe")
+            .build()
+            .unwrap();
+        assert_eq!(person.creator_type(), &CreatorType::SeriesEditor);
+        assert_eq!(person.short_name(), "J. Doe");
+
+        let institution = CreatorBuilder::default()
+            .creator_type(CreatorType::Author)
+            .name("WHO")
+            .build()
+            .unwrap();
+        assert!(institution.is_single_field());
+
+        let missing_type = CreatorBuilder::default().name("WHO").build();
+        assert_eq!(
+            missing_type,
+            Err(CreatorBuilderError::UninitializedField("creator_type"))
+        );
+
+        let mixed = CreatorBuilder::default()
+            .creator_type("author")
+            .name("WHO")
+            .last_name("Doe")
+            .build();
+        assert!(mixed.is_err());
+    }
+}
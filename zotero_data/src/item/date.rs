@@ -0,0 +1,402 @@
+//! Parsing of the free-form dates stored in Zotero items.
+//!
+//! Zotero keeps dates as typed by the user ("Spring 1998", "c. 1850", "3 mars 2021", "2020-02-00", ...)
+//! and only extracts a year, a month and a day when it needs them, with its `strToDate` function.
+//! [`ZoteroDate`] follows the same rules and keeps track of how precise the date is.
+//!
+//! ```rust
+//! use zotero_data::item::{DatePrecision, ZoteroDate};
+//!
+//! let date: ZoteroDate = "3rd March 2021".parse().unwrap();
+//! assert_eq!(date.precision(), DatePrecision::Day);
+//! assert_eq!(date.sort_key(), "2021-03-03");
+//!
+//! let date: ZoteroDate = "circa 1850".parse().unwrap();
+//! assert!(date.circa);
+//! assert_eq!(date.sort_key(), "1850-00-00");
+//! ```
+
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+
+static ISO_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{3,4})[-/.](\d{1,2})(?:[-/.](\d{1,2}))?(?:$|[T\s])").unwrap());
+
+static NUMERIC_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{1,2})[-/.](\d{1,2})[-/.](\d{4})$").unwrap());
+
+static MONTH_YEAR_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{1,2})[-/.](\d{4})$").unwrap());
+
+static PARSED_DATE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d{4})(?:-(\d{2})(?:-(\d{2}))?)?$").unwrap());
+
+static NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
+
+/// Month names in English, French, German, Spanish, Italian and Portuguese.
+/// Any prefix of at least three letters is accepted as well, e.g. `Sept` or `févr`.
+const MONTHS: [(u32, &[&str]); 12] = [
+    (
+        1,
+        &[
+            "january", "janvier", "januar", "enero", "gennaio", "janeiro",
+        ],
+    ),
+    (
+        2,
+        &[
+            "february",
+            "février",
+            "fevrier",
+            "februar",
+            "febrero",
+            "febbraio",
+            "fevereiro",
+        ],
+    ),
+    (3, &["march", "mars", "märz", "maerz", "marzo", "março"]),
+    (4, &["april", "avril", "abril", "aprile"]),
+    (5, &["may", "mai", "mayo", "maggio", "maio"]),
+    (6, &["june", "juin", "juni", "junio", "giugno", "junho"]),
+    (7, &["july", "juillet", "juli", "julio", "luglio", "julho"]),
+    (8, &["august", "août", "aout", "agosto"]),
+    (
+        9,
+        &[
+            "september",
+            "septembre",
+            "septiembre",
+            "setiembre",
+            "settembre",
+            "setembro",
+        ],
+    ),
+    (
+        10,
+        &[
+            "october", "octobre", "oktober", "octubre", "ottobre", "outubro",
+        ],
+    ),
+    (11, &["november", "novembre", "noviembre", "novembro"]),
+    (
+        12,
+        &[
+            "december",
+            "décembre",
+            "decembre",
+            "dezember",
+            "diciembre",
+            "dicembre",
+            "dezembro",
+        ],
+    ),
+];
+
+const SEASONS: [(Season, &[&str]); 4] = [
+    (
+        Season::Spring,
+        &["spring", "printemps", "frühling", "frühjahr", "primavera"],
+    ),
+    (
+        Season::Summer,
+        &[
+            "summer", "été", "ete", "sommer", "verano", "estate", "verão",
+        ],
+    ),
+    (
+        Season::Autumn,
+        &[
+            "autumn", "fall", "automne", "herbst", "otoño", "autunno", "outono",
+        ],
+    ),
+    (Season::Winter, &["winter", "hiver", "invierno", "inverno"]),
+];
+
+const CIRCA: [&str; 7] = [
+    "circa",
+    "ca",
+    "c",
+    "around",
+    "about",
+    "approx",
+    "approximately",
+];
+
+/// How much of a [`ZoteroDate`] is known.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DatePrecision {
+    Year,
+    Season,
+    Month,
+    Day,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+/// A possibly partial date extracted from a Zotero date field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZoteroDate {
+    pub year: i32,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+    pub season: Option<Season>,
+    /// The date is approximate, e.g. "c. 1850".
+    pub circa: bool,
+}
+
+impl ZoteroDate {
+    /// A date known to the year.
+    pub fn year(year: i32) -> ZoteroDate {
+        ZoteroDate {
+            year,
+            month: None,
+            day: None,
+            season: None,
+            circa: false,
+        }
+    }
+
+    /// Build a date from its parts. `0` stands for an unknown month or day, as in Zotero.
+    /// Returns `None` if the date does not exist, e.g. `2020-02-31`.
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<ZoteroDate> {
+        match (month, day) {
+            (0, 0) => Some(ZoteroDate::year(year)),
+            (1..=12, 0) => Some(ZoteroDate {
+                month: Some(month),
+                ..ZoteroDate::year(year)
+            }),
+            _ => NaiveDate::from_ymd_opt(year, month, day).map(|_| ZoteroDate {
+                month: Some(month),
+                day: Some(day),
+                ..ZoteroDate::year(year)
+            }),
+        }
+    }
+
+    /// Parse the `parsedDate` of [`ItemMeta`](crate::item::ItemMeta), which the Zotero API computes from the
+    /// item's date as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`.
+    pub fn from_parsed_date(parsed_date: &str) -> Option<ZoteroDate> {
+        let captures = PARSED_DATE_REGEX.captures(parsed_date)?;
+        let part = |i: usize| {
+            captures
+                .get(i)
+                .and_then(|m| m.as_str().parse::<u32>().ok())
+                .unwrap_or(0)
+        };
+        ZoteroDate::from_ymd(captures[1].parse().ok()?, part(2), part(3))
+    }
+
+    pub fn precision(&self) -> DatePrecision {
+        match (self.day, self.month, self.season) {
+            (Some(_), _, _) => DatePrecision::Day,
+            (None, Some(_), _) => DatePrecision::Month,
+            (None, None, Some(_)) => DatePrecision::Season,
+            (None, None, None) => DatePrecision::Year,
+        }
+    }
+
+    /// A key sorting dates chronologically, in Zotero's `YYYY-MM-DD` form with `00` for unknown parts.
+    pub fn sort_key(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}",
+            self.year,
+            self.month.unwrap_or(0),
+            self.day.unwrap_or(0)
+        )
+    }
+
+    /// The first day of the period covered by this date.
+    pub fn first_day(&self) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.year, self.month.unwrap_or(1), self.day.unwrap_or(1))
+    }
+}
+
+/// Partial ISO form (`2020`, `2020-03` or `2020-03-05`). Seasons and approximations are not written.
+impl fmt::Display for ZoteroDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}", self.year)?;
+        if let Some(month) = self.month {
+            write!(f, "-{:02}", month)?;
+            if let Some(day) = self.day {
+                write!(f, "-{:02}", day)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ZoteroDate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("\"{}\" is not a valid date", s);
+        let date = s.trim();
+        let lowercase = date.to_lowercase();
+        let words: Vec<&str> = lowercase
+            .split(|c: char| !c.is_alphabetic())
+            .filter(|w| !w.is_empty())
+            .collect();
+        let circa = words.first().is_some_and(|w| CIRCA.contains(w));
+
+        let numeric = match (
+            ISO_REGEX.captures(date),
+            NUMERIC_REGEX.captures(date),
+            MONTH_YEAR_REGEX.captures(date),
+        ) {
+            (Some(c), _, _) => Some((
+                c[1].parse().map_err(|_| invalid())?,
+                c[2].parse().map_err(|_| invalid())?,
+                c.get(3)
+                    .map_or(Ok(0), |d| d.as_str().parse())
+                    .map_err(|_| invalid())?,
+            )),
+            // Month first, as in Zotero's default (en-US) locale, unless it can only be a day
+            (_, Some(c), _) => {
+                let first: u32 = c[1].parse().map_err(|_| invalid())?;
+                let second: u32 = c[2].parse().map_err(|_| invalid())?;
+                let year: i32 = c[3].parse().map_err(|_| invalid())?;
+                match first > 12 {
+                    true => Some((year, second, first)),
+                    false => Some((year, first, second)),
+                }
+            }
+            (_, _, Some(c)) => Some((
+                c[2].parse().map_err(|_| invalid())?,
+                c[1].parse().map_err(|_| invalid())?,
+                0,
+            )),
+            _ => None,
+        };
+        if let Some((year, month, day)) = numeric {
+            let mut date = ZoteroDate::from_ymd(year, month, day).ok_or_else(invalid)?;
+            date.circa = circa;
+            return Ok(date);
+        }
+
+        let numbers: Vec<&str> = NUMBER_REGEX.find_iter(date).map(|m| m.as_str()).collect();
+        let year = numbers
+            .iter()
+            .find(|n| (3..=4).contains(&n.len()))
+            .and_then(|n| n.parse::<i32>().ok())
+            .ok_or_else(invalid)?;
+        let month = words.iter().find_map(|word| month_number(word));
+        let season = words.iter().find_map(|word| season(word));
+        let day = match month {
+            Some(_) => numbers
+                .iter()
+                .filter(|n| n.len() <= 2)
+                .filter_map(|n| n.parse::<u32>().ok())
+                .find(|n| (1..=31).contains(n)),
+            None => None,
+        };
+
+        let mut date =
+            ZoteroDate::from_ymd(year, month.unwrap_or(0), day.unwrap_or(0)).ok_or_else(invalid)?;
+        date.season = season;
+        date.circa = circa;
+        Ok(date)
+    }
+}
+
+fn month_number(word: &str) -> Option<u32> {
+    MONTHS.iter().find_map(|(number, names)| {
+        names
+            .iter()
+            .any(|name| *name == word || (word.chars().count() >= 3 && name.starts_with(word)))
+            .then_some(*number)
+    })
+}
+
+fn season(word: &str) -> Option<Season> {
+    SEASONS
+        .iter()
+        .find_map(|(season, names)| names.contains(&word).then_some(*season))
+}
+
+#[cfg(test)]
+mod test_zotero_date {
+    use super::*;
+
+    #[test]
+    fn numeric_dates() {
+        assert_eq!(
+            "2020-02-29".parse::<ZoteroDate>(),
+            Ok(ZoteroDate::from_ymd(2020, 2, 29).unwrap())
+        );
+        assert_eq!(
+            "2019-10-01T13:34:30Z"
+                .parse::<ZoteroDate>()
+                .unwrap()
+                .sort_key(),
+            "2019-10-01"
+        );
+        assert_eq!(
+            "2020-00-00".parse::<ZoteroDate>().unwrap().precision(),
+            DatePrecision::Year
+        );
+        assert_eq!(
+            "03/2021".parse::<ZoteroDate>().unwrap().sort_key(),
+            "2021-03-00"
+        );
+        assert_eq!(
+            "12/25/2021".parse::<ZoteroDate>().unwrap().sort_key(),
+            "2021-12-25"
+        );
+        assert_eq!(
+            "25.12.2021".parse::<ZoteroDate>().unwrap().sort_key(),
+            "2021-12-25"
+        );
+        assert!("2020-02-31".parse::<ZoteroDate>().is_err());
+        assert!("13/31/2021".parse::<ZoteroDate>().is_err());
+    }
+
+    #[test]
+    fn textual_dates() {
+        let date = "March 3rd, 2021".parse::<ZoteroDate>().unwrap();
+        assert_eq!(date.sort_key(), "2021-03-03");
+
+        let date = "1er févr. 1998".parse::<ZoteroDate>().unwrap();
+        assert_eq!(date.sort_key(), "1998-02-01");
+
+        let date = "12. Dezember 1901".parse::<ZoteroDate>().unwrap();
+        assert_eq!(date.sort_key(), "1901-12-12");
+
+        let date = "Sept 1975".parse::<ZoteroDate>().unwrap();
+        assert_eq!(date.precision(), DatePrecision::Month);
+
+        let date = "Spring 1998".parse::<ZoteroDate>().unwrap();
+        assert_eq!(date.season, Some(Season::Spring));
+        assert_eq!(date.precision(), DatePrecision::Season);
+
+        let date = "c. 1850".parse::<ZoteroDate>().unwrap();
+        assert!(date.circa);
+        assert_eq!(date.precision(), DatePrecision::Year);
+
+        assert!("unknown".parse::<ZoteroDate>().is_err());
+        assert!("31 February 2020".parse::<ZoteroDate>().is_err());
+    }
+
+    #[test]
+    fn parsed_date_hint() {
+        assert_eq!(
+            ZoteroDate::from_parsed_date("2019-02"),
+            ZoteroDate::from_ymd(2019, 2, 0)
+        );
+        assert_eq!(ZoteroDate::from_parsed_date("25-2-2019"), None);
+        assert_eq!(
+            ZoteroDate::from_parsed_date("2019-02-25")
+                .unwrap()
+                .to_string(),
+            "2019-02-25"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Field-level differences between two versions of an item.
+//!
+//! [`ItemPatch`] serializes to the minimal body of a PATCH request, so that fields changed by someone else in
+//! the meantime are not overwritten.
+//!
+//! ```rust
+//! use zotero_data::item::{diff, BookDataBuilder, ItemType};
+//!
+//! let old = ItemType::Book(BookDataBuilder::default().title("Lorem").build().unwrap());
+//! let mut new = old.clone();
+//! if let ItemType::Book(book) = &mut new {
+//!     book.title = "Ipsum".to_string();
+//! }
+//!
+//! let patch = diff(&old, &new);
+//! assert_eq!(serde_json::to_string(&patch).unwrap(), r#"{"title":"Ipsum"}"#);
+//! assert_eq!(patch.to_string(), "title: \"Lorem\" -> \"Ipsum\"\n");
+//! ```
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::{Map, Value};
+use std::fmt;
+
+use crate::item::{Creator, ItemType};
+
+/// Fields managed by Zotero, never part of a patch.
+pub(crate) const IGNORED_FIELDS: [&str; 4] = ["key", "version", "dateAdded", "dateModified"];
+
+/// A changed field, with its Zotero name and its old and new JSON values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+impl FieldChange {
+    /// Elements of a list field (creators, tags, collections, ...) present only in the new value.
+    pub fn added(&self) -> Vec<&Value> {
+        list_difference(&self.new, &self.old)
+    }
+
+    /// Elements of a list field present only in the old value.
+    pub fn removed(&self) -> Vec<&Value> {
+        list_difference(&self.old, &self.new)
+    }
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.old, &self.new) {
+            // a list can also be `null`, e.g. the collections of a new item
+            (Value::Array(_), Value::Array(_) | Value::Null) | (Value::Null, Value::Array(_)) => {
+                let added = self.added();
+                let removed = self.removed();
+                let mut parts = Vec::new();
+                if !added.is_empty() {
+                    parts.push(format!("added {}", describe_elements(&self.field, &added)));
+                }
+                if !removed.is_empty() {
+                    parts.push(format!(
+                        "removed {}",
+                        describe_elements(&self.field, &removed)
+                    ));
+                }
+                if parts.is_empty() {
+                    parts.push("reordered".to_string());
+                }
+                write!(f, "{}: {}", self.field, parts.join("; "))
+            }
+            (old, new) => write!(f, "{}: {} -> {}", self.field, old, new),
+        }
+    }
+}
+
+/// The changes between two versions of an item, as returned by [`diff`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemPatch {
+    pub changes: Vec<FieldChange>,
+}
+
+impl ItemPatch {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The change of a field, by its Zotero name.
+    pub fn get(&self, field: &str) -> Option<&FieldChange> {
+        self.changes.iter().find(|c| c.field == field)
+    }
+}
+
+/// Serialize to the body of a PATCH request: an object with the new value of every changed field.
+impl Serialize for ItemPatch {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.changes.len()))?;
+        for change in &self.changes {
+            map.serialize_entry(&change.field, &change.new)?;
+        }
+        map.end()
+    }
+}
+
+/// One line per changed field, for review before sending the patch.
+impl fmt::Display for ItemPatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compute the fields changed between `old` and `new`, including creators, tags, collections and relations.
+/// The key, the version and the dates managed by Zotero are ignored.
+pub fn diff(old: &ItemType, new: &ItemType) -> ItemPatch {
+    let old = as_object(old);
+    let new = as_object(new);
+
+    let mut changes = Vec::new();
+    for (field, new_value) in &new {
+        let old_value = old
+            .get(field)
+            .cloned()
+            .unwrap_or_else(|| empty_like(new_value));
+        if !IGNORED_FIELDS.contains(&field.as_str()) && old_value != *new_value {
+            changes.push(FieldChange {
+                field: field.clone(),
+                old: old_value,
+                new: new_value.clone(),
+            });
+        }
+    }
+    // fields left out of the serialization when empty, e.g. creators, have been cleared
+    for (field, old_value) in &old {
+        let new_value = empty_like(old_value);
+        if !new.contains_key(field)
+            && !IGNORED_FIELDS.contains(&field.as_str())
+            && *old_value != new_value
+        {
+            changes.push(FieldChange {
+                field: field.clone(),
+                old: old_value.clone(),
+                new: new_value,
+            });
+        }
+    }
+    ItemPatch { changes }
+}
+
+pub(crate) fn as_object(item: &ItemType) -> Map<String, Value> {
+    match serde_json::to_value(item) {
+        Ok(Value::Object(object)) => object,
+        _ => Map::new(),
+    }
+}
+
+/// The value clearing a field that is missing from an item, e.g. `0` for the `deleted` flag of a restored item.
+pub(crate) fn empty_like(value: &Value) -> Value {
+    match value {
+        Value::String(_) => Value::String(String::new()),
+        Value::Number(_) => Value::from(0),
+        Value::Bool(_) => Value::Bool(false),
+        Value::Array(_) => Value::Array(Vec::new()),
+        Value::Object(_) => Value::Object(Map::new()),
+        _ => Value::Null,
+    }
+}
+
+fn list_difference<'a>(list: &'a Value, other: &Value) -> Vec<&'a Value> {
+    match (list, other) {
+        (Value::Array(list), Value::Array(other)) => {
+            list.iter().filter(|v| !other.contains(v)).collect()
+        }
+        (Value::Array(list), Value::Null) => list.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn describe_elements(field: &str, elements: &[&Value]) -> String {
+    elements
+        .iter()
+        .map(|element| match field {
+            "creators" => match serde_json::from_value::<Creator>((*element).clone()) {
+                Ok(creator) => format!("{} ({})", creator.full_name(), creator.creator_type()),
+                Err(_) => element.to_string(),
+            },
+            "tags" => match element.get("tag") {
+                Some(tag) => tag.to_string(),
+                None => element.to_string(),
+            },
+            _ => element.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod test_diff {
+    use super::*;
+    use crate::item::{JournalArticleData, JournalArticleDataBuilder};
+    use crate::shared_fields::Tag;
+
+    fn article() -> JournalArticleData {
+        let mut article = JournalArticleDataBuilder::default()
+            .title("Lorem")
+            .creators(vec![Creator::two_field("author", "John", "Doe")])
+            .tags(vec![Tag {
+                tag: "history".to_string(),
+            }])
+            .build()
+            .unwrap();
+        article.key = "ABCD2345".to_string();
+        article.version = 12;
+        article
+    }
+
+    #[test]
+    fn minimal_patch() {
+        let old = article();
+        let mut new = article();
+        new.version = 13;
+        new.volume = "4".to_string();
+        new.creators
+            .push(Creator::two_field("editor", "Jane", "Doe"));
+        new.tags.clear();
+        new.collections = Some(vec!["TYPDZEZF".to_string()]);
+
+        let patch = diff(
+            &ItemType::JournalArticle(old),
+            &ItemType::JournalArticle(new),
+        );
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            serde_json::json!({
+                "volume": "4",
+                "creators": [
+                    {"creatorType": "author", "firstName": "John", "lastName": "Doe"},
+                    {"creatorType": "editor", "firstName": "Jane", "lastName": "Doe"}
+                ],
+                "tags": [],
+                "collections": ["TYPDZEZF"]
+            })
+        );
+
+        let report = patch.to_string();
+        assert!(report.contains("volume: \"\" -> \"4\""));
+        assert!(report.contains("creators: added Jane Doe (editor)"));
+        assert!(report.contains("tags: removed \"history\""));
+        assert!(report.contains("collections: added \"TYPDZEZF\""));
+    }
+
+    #[test]
+    fn cleared_creators() {
+        let old = article();
+        let mut new = article();
+        new.creators.clear();
+
+        let patch = diff(
+            &ItemType::JournalArticle(old),
+            &ItemType::JournalArticle(new),
+        );
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            serde_json::json!({"creators": []})
+        );
+        assert_eq!(patch.get("creators").unwrap().removed().len(), 1);
+    }
+
+    #[test]
+    fn restored_item() {
+        let mut old = article();
+        old.deleted = true;
+        let new = article();
+
+        let patch = diff(
+            &ItemType::JournalArticle(old),
+            &ItemType::JournalArticle(new),
+        );
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            serde_json::json!({"deleted": 0})
+        );
+    }
+
+    #[test]
+    fn identical_items() {
+        let item = ItemType::JournalArticle(article());
+        assert!(diff(&item, &item).is_empty());
+    }
+}
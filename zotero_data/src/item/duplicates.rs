@@ -0,0 +1,448 @@
+//! Duplicate detection, following the heuristics of Zotero desktop's "Duplicate Items" view.
+//!
+//! Two items are duplicates when they share a DOI or, for books, an ISBN. Otherwise their titles must be
+//! (nearly) identical once normalized, and the match is rejected when:
+//! - both items have a DOI, or both have an ISBN, and they differ;
+//! - both items have a year and the years are more than one year apart;
+//! - both items have creators and no creator has the same last name and first initial.
+//!
+//! Annotations, attachments and notes are never reported as duplicates.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::item::validation::split_identifiers;
+use crate::item::{Creator, Item, ItemType, ItemTypeKind};
+use crate::shared_fields::{ItemCommon, ItemFields};
+
+static DOI_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"10\.\d{4,9}/\S*[^\s.,]").unwrap());
+
+/// Normalized titles at least this similar are considered the same title.
+const TITLE_SIMILARITY_THRESHOLD: f64 = 0.9;
+
+/// Titles are only compared with the following titles in alphabetical order sharing this many characters.
+const TITLE_PREFIX_LENGTH: usize = 4;
+
+/// Why items were grouped in a [`DuplicateCluster`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchReason {
+    Doi,
+    Isbn,
+    Title,
+}
+
+/// A group of items considered to be the same work.
+#[derive(Clone, Debug)]
+pub struct DuplicateCluster<'a> {
+    pub items: Vec<&'a Item>,
+    /// Between 0 and 1. Identifier matches are certain, title matches are less so when titles differ slightly or
+    /// when the year or the creators are missing on one side.
+    pub confidence: f64,
+    pub reasons: Vec<MatchReason>,
+}
+
+impl DuplicateCluster<'_> {
+    pub fn keys(&self) -> Vec<&str> {
+        self.items.iter().map(|item| item.key.as_str()).collect()
+    }
+}
+
+/// What the heuristics need to know about an item, computed once.
+struct Candidate {
+    title: String,
+    dois: BTreeSet<String>,
+    isbns: BTreeSet<String>,
+    year: Option<i32>,
+    creators: Vec<(String, Option<char>)>,
+}
+
+impl Candidate {
+    fn new(item: &Item) -> Candidate {
+        let is_book = matches!(
+            item.data.kind(),
+            Some(ItemTypeKind::Book | ItemTypeKind::BookSection)
+        );
+        let mut dois: BTreeSet<String> = item
+            .data
+            .doi()
+            .and_then(normalize_doi)
+            .into_iter()
+            .collect();
+        // Zotero keeps the DOI of item types without a DOI field in `extra`
+        if let Some(extra) = item.data.extra() {
+            dois.extend(
+                extra
+                    .lines()
+                    .filter(|line| line.trim_start().to_lowercase().starts_with("doi:"))
+                    .filter_map(normalize_doi),
+            );
+        }
+        Candidate {
+            title: normalize_title(item.data.title()),
+            dois,
+            isbns: match (is_book, item.data.get_field("ISBN")) {
+                (true, Some(isbn)) => split_identifiers(isbn).filter_map(normalize_isbn).collect(),
+                _ => BTreeSet::new(),
+            },
+            year: item.date().map(|date| date.year),
+            creators: item.data.creators().iter().map(creator_name).collect(),
+        }
+    }
+}
+
+/// Find groups of duplicate items.
+///
+/// ```rust
+/// use zotero_data::item::{find_duplicates, Item};
+///
+/// let items: Vec<Item> = Vec::new();
+/// for cluster in find_duplicates(&items) {
+///     println!("{:?} ({:.0}%)", cluster.keys(), cluster.confidence * 100.0);
+/// }
+/// ```
+pub fn find_duplicates(items: &[Item]) -> Vec<DuplicateCluster<'_>> {
+    let candidates: Vec<(usize, Candidate)> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            !matches!(
+                item.data,
+                ItemType::Annotation(_)
+                    | ItemType::Attachment(_)
+                    | ItemType::Note(_)
+                    | ItemType::Unknown(_)
+            )
+        })
+        .map(|(i, item)| (i, Candidate::new(item)))
+        .collect();
+
+    let mut matches: Vec<(usize, usize, f64, MatchReason)> = Vec::new();
+
+    // identifiers, each matched with the first item having it; a pair sharing a DOI isn't matched again by ISBN
+    let mut identified: HashSet<(usize, usize)> = HashSet::new();
+    for reason in [MatchReason::Doi, MatchReason::Isbn] {
+        let mut first_with: HashMap<&str, usize> = HashMap::new();
+        for (i, candidate) in &candidates {
+            let identifiers = match reason {
+                MatchReason::Doi => &candidate.dois,
+                _ => &candidate.isbns,
+            };
+            for identifier in identifiers {
+                let first = *first_with.entry(identifier.as_str()).or_insert(*i);
+                if first != *i && identified.insert((first, *i)) {
+                    matches.push((first, *i, 1.0, reason));
+                }
+            }
+        }
+    }
+
+    // titles, compared with the next titles in alphabetical order like Zotero does
+    let mut by_title: Vec<&(usize, Candidate)> = candidates
+        .iter()
+        .filter(|(_, c)| !c.title.is_empty())
+        .collect();
+    by_title.sort_by(|a, b| a.1.title.cmp(&b.1.title));
+    for (a, (i, first)) in by_title.iter().map(|c| (c.0, &c.1)).enumerate() {
+        let prefix: String = first.title.chars().take(TITLE_PREFIX_LENGTH).collect();
+        for (j, second) in by_title[a + 1..].iter().map(|c| (c.0, &c.1)) {
+            if !second.title.starts_with(&prefix) {
+                break;
+            }
+            if let Some(confidence) = title_match(first, second) {
+                matches.push((i.min(j), i.max(j), confidence, MatchReason::Title));
+            }
+        }
+    }
+
+    clusters(items, matches)
+}
+
+/// Confidence of a title match between two items, `None` if they are not duplicates.
+fn title_match(first: &Candidate, second: &Candidate) -> Option<f64> {
+    let similarity = similarity(&first.title, &second.title);
+    if similarity < TITLE_SIMILARITY_THRESHOLD {
+        return None;
+    }
+    if (!first.dois.is_empty() && !second.dois.is_empty() && first.dois != second.dois)
+        || (!first.isbns.is_empty() && !second.isbns.is_empty() && first.isbns != second.isbns)
+    {
+        return None;
+    }
+
+    let mut confidence = similarity;
+    match (first.year, second.year) {
+        (Some(a), Some(b)) if (a - b).abs() > 1 => return None,
+        (Some(a), Some(b)) if a != b => confidence *= 0.9,
+        (Some(_), Some(_)) => {}
+        _ => confidence *= 0.95,
+    }
+    match (first.creators.is_empty(), second.creators.is_empty()) {
+        (false, false) => {
+            let shared = first.creators.iter().any(|(last_name, initial)| {
+                second
+                    .creators
+                    .iter()
+                    .any(|(other_last_name, other_initial)| {
+                        last_name == other_last_name
+                            && (initial.is_none()
+                                || other_initial.is_none()
+                                || initial == other_initial)
+                    })
+            });
+            if !shared {
+                return None;
+            }
+        }
+        (true, true) => {}
+        _ => confidence *= 0.95,
+    }
+    Some(confidence)
+}
+
+/// Group matching pairs into clusters. A cluster is as reliable as its weakest match.
+fn clusters(
+    items: &[Item],
+    matches: Vec<(usize, usize, f64, MatchReason)>,
+) -> Vec<DuplicateCluster<'_>> {
+    let mut parents: Vec<usize> = (0..items.len()).collect();
+    fn root(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
+        }
+        i
+    }
+    for (i, j, _, _) in &matches {
+        let (a, b) = (root(&mut parents, *i), root(&mut parents, *j));
+        parents[a.max(b)] = a.min(b);
+    }
+
+    // clusters in the order of their first item, found by their root
+    let matched: HashSet<usize> = matches.iter().flat_map(|(i, j, _, _)| [*i, *j]).collect();
+    let mut clusters: Vec<DuplicateCluster> = Vec::new();
+    let mut cluster_of_root: HashMap<usize, usize> = HashMap::new();
+    for (i, item) in items.iter().enumerate() {
+        if !matched.contains(&i) {
+            continue;
+        }
+        let position = *cluster_of_root
+            .entry(root(&mut parents, i))
+            .or_insert_with(|| {
+                clusters.push(DuplicateCluster {
+                    items: Vec::new(),
+                    confidence: 1.0,
+                    reasons: Vec::new(),
+                });
+                clusters.len() - 1
+            });
+        clusters[position].items.push(item);
+    }
+    for (i, _, confidence, reason) in matches {
+        let cluster = &mut clusters[cluster_of_root[&root(&mut parents, i)]];
+        cluster.confidence = cluster.confidence.min(confidence);
+        if !cluster.reasons.contains(&reason) {
+            cluster.reasons.push(reason);
+        }
+    }
+    for cluster in &mut clusters {
+        cluster.reasons.sort();
+    }
+    clusters
+}
+
+/// Lowercase, without diacritics, punctuation or repeated spaces.
+fn normalize_title(title: &str) -> String {
+    title
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(remove_diacritic)
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn remove_diacritic(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ś' | 'š' => 's',
+        'ź' | 'ż' | 'ž' => 'z',
+        'ł' => 'l',
+        'ř' => 'r',
+        c => c,
+    }
+}
+
+fn normalize_doi(doi: &str) -> Option<String> {
+    DOI_REGEX.find(doi).map(|m| m.as_str().to_lowercase())
+}
+
+/// ISBNs are compared as ISBN-13, without hyphens.
+fn normalize_isbn(isbn: &str) -> Option<String> {
+    let isbn: String = isbn
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == 'X' || *c == 'x')
+        .collect::<String>()
+        .to_uppercase();
+    match isbn.len() {
+        13 => Some(isbn),
+        10 => {
+            let digits = format!("978{}", &isbn[..9]);
+            let sum: u32 = digits
+                .chars()
+                .filter_map(|c| c.to_digit(10))
+                .enumerate()
+                .map(|(i, d)| if i % 2 == 0 { d } else { d * 3 })
+                .sum();
+            Some(format!("{}{}", digits, (10 - sum % 10) % 10))
+        }
+        _ => None,
+    }
+}
+
+fn creator_name(creator: &Creator) -> (String, Option<char>) {
+    let initial = match creator {
+        Creator::TwoField { first_name, .. } => first_name
+            .chars()
+            .next()
+            .and_then(|c| c.to_lowercase().next()),
+        Creator::SingleField { .. } => None,
+    };
+    (normalize_title(creator.last_name()), initial)
+}
+
+/// Similarity of two strings from 0 to 1, based on the Levenshtein distance.
+fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f64 / a.len().max(b.len()) as f64
+}
+
+#[cfg(test)]
+mod test_duplicates {
+    use super::*;
+    use crate::item::{
+        test_item, BookDataBuilder, CreatorBuilder, JournalArticleDataBuilder, NoteDataBuilder,
+    };
+
+    fn article(key: &str, title: &str, doi: &str) -> Item {
+        test_item(ItemType::JournalArticle(
+            JournalArticleDataBuilder::default()
+                .key(key)
+                .title(title)
+                .doi(doi)
+                .build()
+                .unwrap(),
+        ))
+    }
+
+    fn book(key: &str, title: &str, date: &str, isbn: &str, creator: &Creator) -> Item {
+        test_item(ItemType::Book(
+            BookDataBuilder::default()
+                .key(key)
+                .title(title)
+                .date(date)
+                .isbn(isbn)
+                .creators(vec![creator.clone()])
+                .build()
+                .unwrap(),
+        ))
+    }
+
+    fn author(first_name: &str, last_name: &str) -> Creator {
+        CreatorBuilder::default()
+            .creator_type("author")
+            .first_name(first_name)
+            .last_name(last_name)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn identifier_matches() {
+        let doe = author("John", "Doe");
+        let items = vec![
+            article("AAAAAAAA", "Lorem", "10.1000/ABC"),
+            article("BBBBBBBB", "Ipsum", "https://doi.org/10.1000/abc"),
+            book("CCCCCCCC", "Dolor", "", "0-306-40615-2", &doe),
+            book("DDDDDDDD", "Sit amet", "", "978-0-306-40615-7", &doe),
+            book("EEEEEEEE", "Consectetur", "", "978-3-16-148410-0", &doe),
+            book("FFFFFFFF", "Adipiscing", "", "978 0 306 40615 7", &doe),
+        ];
+
+        let clusters = find_duplicates(&items);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].keys(), vec!["AAAAAAAA", "BBBBBBBB"]);
+        assert_eq!(clusters[0].reasons, vec![MatchReason::Doi]);
+        assert_eq!(clusters[1].keys(), vec!["CCCCCCCC", "DDDDDDDD", "FFFFFFFF"]);
+        assert_eq!(clusters[1].reasons, vec![MatchReason::Isbn]);
+        assert_eq!(clusters[1].confidence, 1.0);
+    }
+
+    #[test]
+    fn title_matches() {
+        let doe = author("John", "Doe");
+        let items = vec![
+            book("AAAAAAAA", "The History of Zotero", "2019", "", &doe),
+            book("BBBBBBBB", "The history of Zotéro.", "2020", "", &doe),
+            book("CCCCCCCC", "The Histery of Zotero", "2019", "", &doe),
+            // years too far apart
+            book("DDDDDDDD", "The History of Zotero", "1990", "", &doe),
+            // no creator in common
+            book(
+                "EEEEEEEE",
+                "The History of Zotero",
+                "2019",
+                "",
+                &author("Jane", "Roe"),
+            ),
+            test_item(ItemType::Note(
+                NoteDataBuilder::default()
+                    .key("FFFFFFFF")
+                    .note("The History of Zotero")
+                    .build()
+                    .unwrap(),
+            )),
+        ];
+
+        let clusters = find_duplicates(&items);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].keys(), vec!["AAAAAAAA", "BBBBBBBB", "CCCCCCCC"]);
+        assert_eq!(clusters[0].reasons, vec![MatchReason::Title]);
+        assert!(clusters[0].confidence < 1.0 && clusters[0].confidence > 0.8);
+    }
+
+    #[test]
+    fn normalization() {
+        assert_eq!(
+            normalize_isbn("0-306-40615-2"),
+            Some("9780306406157".to_string())
+        );
+        assert_eq!(
+            normalize_doi("doi: 10.1000/XYZ."),
+            Some("10.1000/xyz".to_string())
+        );
+        assert_eq!(normalize_title("  L'Été, en   Forêt! "), "l ete en foret");
+    }
+}
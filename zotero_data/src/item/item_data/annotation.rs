@@ -0,0 +1,374 @@
+use crate::item::Creator;
+use crate::note;
+use crate::relations::Relations;
+use crate::shared_fields::{FieldError, ItemCommon, ItemFields, Tag, UnknownFields};
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use zotero_derive::{ItemCommon, ItemFields};
+
+/// The kind of an annotation, as stored in `annotationType`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum AnnotationType {
+    #[default]
+    Highlight,
+    Underline,
+    Note,
+    Text,
+    Image,
+    Ink,
+    Other(String),
+}
+
+impl AnnotationType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AnnotationType::Highlight => "highlight",
+            AnnotationType::Underline => "underline",
+            AnnotationType::Note => "note",
+            AnnotationType::Text => "text",
+            AnnotationType::Image => "image",
+            AnnotationType::Ink => "ink",
+            AnnotationType::Other(s) => s,
+        }
+    }
+}
+
+impl fmt::Display for AnnotationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for AnnotationType {
+    fn from(s: &str) -> AnnotationType {
+        match s {
+            "highlight" => AnnotationType::Highlight,
+            "underline" => AnnotationType::Underline,
+            "note" => AnnotationType::Note,
+            "text" => AnnotationType::Text,
+            "image" => AnnotationType::Image,
+            "ink" => AnnotationType::Ink,
+            other => AnnotationType::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for AnnotationType {
+    fn from(s: String) -> AnnotationType {
+        AnnotationType::from(s.as_str())
+    }
+}
+
+impl From<AnnotationType> for String {
+    fn from(annotation_type: AnnotationType) -> String {
+        annotation_type.as_str().to_string()
+    }
+}
+
+/// Where an annotation is, parsed from `annotationPosition`.
+///
+/// PDF annotations have a page index with rectangles (highlights, underlines, notes, texts and images) or paths
+/// (ink). Each rectangle is `[x1, y1, x2, y2]` in PDF points, each path a flat list of `x, y` coordinates.
+/// EPUB and snapshot annotations use selectors instead, kept in `unknown_fields`.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationPosition {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub page_index: Option<usize>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub rects: Vec<[f64; 4]>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub paths: Vec<Vec<f64>>,
+    /// Stroke width of ink annotations.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub width: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub rotation: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub font_size: Option<f64>,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
+}
+
+impl AnnotationPosition {
+    /// Points of each ink path, as `(x, y)` pairs.
+    pub fn points(&self) -> Vec<Vec<(f64, f64)>> {
+        self.paths
+            .iter()
+            .map(|path| {
+                path.chunks_exact(2)
+                    .map(|point| (point[0], point[1]))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// An annotation made in the Zotero reader, a child item of the annotated attachment.
+#[derive(Deserialize, Serialize, Clone, Debug, Builder, ItemCommon, ItemFields)]
+#[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
+#[builder(setter(into), default)]
+pub struct AnnotationData {
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub key: String,
+    #[builder(setter(skip))]
+    #[serde(default)]
+    pub version: usize,
+    /// Annotations have no title.
+    #[builder(setter(skip))]
+    #[serde(skip)]
+    pub title: String,
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub parent_item: String,
+    #[builder(setter(skip))]
+    #[serde(default = "default_document_type")]
+    pub item_type: String,
+    pub annotation_type: AnnotationType,
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub annotation_author_name: String,
+    /// The highlighted or underlined text.
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub annotation_text: String,
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub annotation_comment: String,
+    /// Hex color, e.g. `#ffd400`.
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub annotation_color: String,
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub annotation_page_label: String,
+    /// Orders the annotations of an attachment, e.g. `00000|001234|00567` for page, offset and top.
+    #[serde(default)]
+    pub annotation_sort_index: String,
+    /// JSON of an [`AnnotationPosition`], see [`AnnotationData::position`].
+    #[serde(default)]
+    pub annotation_position: String,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    #[serde(default)]
+    pub relations: Relations,
+    /// Set when the item is in the trash.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::shared_fields::deleted::is_false",
+        with = "crate::shared_fields::deleted"
+    )]
+    pub deleted: bool,
+    #[serde(skip_serializing, default)]
+    pub date_added: String,
+    #[serde(skip_serializing, default)]
+    pub date_modified: String,
+    #[serde(flatten)]
+    #[builder(setter(skip))]
+    pub unknown_fields: UnknownFields,
+}
+
+fn default_document_type() -> String {
+    "annotation".to_string()
+}
+
+impl Default for AnnotationData {
+    fn default() -> Self {
+        AnnotationData {
+            key: String::default(),
+            version: 0,
+            title: String::default(),
+            parent_item: String::default(),
+            item_type: default_document_type(),
+            annotation_type: AnnotationType::default(),
+            annotation_author_name: String::default(),
+            annotation_text: String::default(),
+            annotation_comment: String::default(),
+            annotation_color: String::default(),
+            annotation_page_label: String::default(),
+            annotation_sort_index: String::default(),
+            annotation_position: String::default(),
+            tags: Vec::default(),
+            relations: Relations::default(),
+            deleted: false,
+            date_added: String::default(),
+            date_modified: String::default(),
+            unknown_fields: UnknownFields::default(),
+        }
+    }
+}
+
+impl AnnotationData {
+    /// Parse `annotationPosition`.
+    pub fn position(&self) -> Result<AnnotationPosition, serde_json::Error> {
+        serde_json::from_str(&self.annotation_position)
+    }
+
+    pub fn set_position(&mut self, position: &AnnotationPosition) {
+        self.annotation_position = serde_json::to_string(position).unwrap_or_default();
+    }
+
+    /// Markdown for the annotation in a report: the quoted text, the page and the comment.
+    fn to_markdown(&self) -> String {
+        let page = match self.annotation_page_label.is_empty() {
+            true => String::new(),
+            false => format!(
+                " (p. {})",
+                note::escape_markdown(&self.annotation_page_label)
+            ),
+        };
+        let mut markdown = match self.annotation_type {
+            AnnotationType::Highlight | AnnotationType::Underline => format!(
+                "> “{}”{}",
+                note::escape_markdown(
+                    &self
+                        .annotation_text
+                        .split_whitespace()
+                        .collect::<Vec<&str>>()
+                        .join(" ")
+                ),
+                page
+            ),
+            AnnotationType::Image => format!("*Image annotation{}*", page),
+            AnnotationType::Ink => format!("*Ink annotation{}*", page),
+            _ => format!("*Note{}*", page),
+        };
+        let comment = self.annotation_comment.trim();
+        if !comment.is_empty() {
+            markdown.push_str("\n\n");
+            markdown.push_str(&note::escape_markdown(comment));
+        }
+        markdown
+    }
+}
+
+/// Render annotations as Markdown, in their order in the document, e.g. to write the report of a reading.
+pub fn annotations_to_markdown(annotations: &[AnnotationData]) -> String {
+    let mut annotations: Vec<&AnnotationData> = annotations.iter().collect();
+    annotations.sort_by(|a, b| a.annotation_sort_index.cmp(&b.annotation_sort_index));
+    annotations
+        .iter()
+        .map(|annotation| annotation.to_markdown())
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// Render annotations as note HTML, e.g. to create a note from the annotations of an attachment.
+pub fn annotations_to_html(annotations: &[AnnotationData]) -> String {
+    note::markdown_to_html(&annotations_to_markdown(annotations))
+}
+
+use crate::ToJson;
+impl ToJson for AnnotationData {}
+
+#[cfg(test)]
+mod test_annotation {
+    use super::*;
+    use crate::item::ItemType;
+
+    #[test]
+    fn highlight() {
+        let highlight = match serde_json::from_value::<ItemType>(serde_json::json!({
+            "key": "ANNOTAT1",
+            "version": 4,
+            "parentItem": "ATTACHM1",
+            "itemType": "annotation",
+            "annotationType": "highlight",
+            "annotationText": "Lorem\nipsum",
+            "annotationComment": "Important",
+            "annotationColor": "#ffd400",
+            "annotationPageLabel": "12",
+            "annotationSortIndex": "00011|000123|00456",
+            "annotationPosition": "{\"pageIndex\":11,\"rects\":[[72.5,600.1,300,612.4]]}",
+            "tags": [],
+            "relations": {},
+            "dateAdded": "2023-01-01T00:00:00Z",
+            "dateModified": "2023-01-01T00:00:00Z"
+        }))
+        .unwrap()
+        {
+            ItemType::Annotation(annotation) => annotation,
+            other => panic!("not an annotation: {:?}", other),
+        };
+        assert_eq!(highlight.annotation_type, AnnotationType::Highlight);
+        assert_eq!(highlight.parent_item().unwrap(), "ATTACHM1");
+
+        let position = highlight.position().unwrap();
+        assert_eq!(position.page_index, Some(11));
+        assert_eq!(position.rects, vec![[72.5, 600.1, 300.0, 612.4]]);
+
+        let value = serde_json::to_value(&highlight).unwrap();
+        assert_eq!(value["annotationType"], "highlight");
+        assert_eq!(value["itemType"], "annotation");
+        assert!(value.get("title").is_none());
+    }
+
+    #[test]
+    fn ink_and_unknown_positions() {
+        let mut ink = AnnotationDataBuilder::default()
+            .annotation_type(AnnotationType::Ink)
+            .annotation_position("{\"pageIndex\":0,\"width\":2,\"paths\":[[10,20,11,21,12,22]]}")
+            .build()
+            .unwrap();
+        let position = ink.position().unwrap();
+        assert_eq!(position.width, Some(2.0));
+        assert_eq!(
+            position.points(),
+            vec![vec![(10.0, 20.0), (11.0, 21.0), (12.0, 22.0)]]
+        );
+        ink.set_position(&AnnotationPosition {
+            page_index: Some(1),
+            ..position
+        });
+        assert_eq!(ink.position().unwrap().page_index, Some(1));
+
+        let epub = AnnotationDataBuilder::default()
+            .annotation_type(AnnotationType::Underline)
+            .annotation_position("{\"type\":\"FragmentSelector\",\"value\":\"epubcfi(/6/4)\"}")
+            .build()
+            .unwrap();
+        let position = epub.position().unwrap();
+        assert_eq!(position.page_index, None);
+        assert_eq!(position.unknown_fields["type"], "FragmentSelector");
+
+        let future: AnnotationData =
+            serde_json::from_str(r#"{"annotationType": "shape"}"#).unwrap();
+        assert_eq!(
+            future.annotation_type,
+            AnnotationType::Other("shape".to_string())
+        );
+    }
+
+    #[test]
+    fn report() {
+        let annotations = vec![
+            AnnotationDataBuilder::default()
+                .annotation_type(AnnotationType::Note)
+                .annotation_comment("Check *this*")
+                .annotation_page_label("3")
+                .annotation_sort_index("00002|000000|00100")
+                .build()
+                .unwrap(),
+            AnnotationDataBuilder::default()
+                .annotation_type(AnnotationType::Highlight)
+                .annotation_text("Lorem ipsum")
+                .annotation_page_label("1")
+                .annotation_sort_index("00000|000010|00200")
+                .build()
+                .unwrap(),
+            AnnotationDataBuilder::default()
+                .annotation_type(AnnotationType::Image)
+                .annotation_page_label("2")
+                .annotation_sort_index("00001|000000|00000")
+                .build()
+                .unwrap(),
+        ];
+
+        assert_eq!(
+            annotations_to_markdown(&annotations),
+            "> “Lorem ipsum” (p. 1)\n\n*Image annotation (p. 2)*\n\n*Note (p. 3)*\n\nCheck \\*this\\*"
+        );
+        assert_eq!(
+            annotations_to_html(&annotations),
+            "<blockquote>\n<p>“Lorem ipsum” (p. 1)</p>\n</blockquote>\n<p><em>Image annotation (p. 2)</em></p>\n\
+             <p><em>Note (p. 3)</em></p>\n<p>Check *this*</p>\n"
+        );
+    }
+}
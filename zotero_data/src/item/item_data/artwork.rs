@@ -1,67 +0,0 @@
-use crate::item::Creator;
-use crate::shared_fields::{ItemCommon, Tag};
-use derive_builder::Builder;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-
-use zotero_derive::ItemCommon;
-
-/// A piece of artwork (e.g., an oil painting, photograph, or sculpture). Also use this item type for other types of images or visual items (e.g., scientific figures).
-#[derive(Default, Deserialize, Serialize, Clone, Debug, Builder, ItemCommon)]
-#[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
-#[builder(setter(into), default)]
-pub struct ArtworkData {
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub key: String,
-    #[builder(setter(skip))]
-    pub version: usize,
-    #[builder(setter(skip))]
-    #[serde(default = "default_document_type")]
-    pub item_type: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub title: String,
-    #[serde(skip_serializing_if = "Vec::is_empty", default)]
-    pub creators: Vec<Creator>,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub abstract_note: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub artwork_medium: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub artwork_size: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub date: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub language: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub short_title: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub archive: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub archive_location: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub library_catalog: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub call_number: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub url: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub access_date: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub rights: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub extra: String,
-    pub tags: Vec<Tag>,
-    pub collections: Option<Vec<String>>,
-    pub relations: HashMap<String, String>,
-    #[serde(skip_serializing)]
-    pub date_added: String,
-    #[serde(skip_serializing)]
-    pub date_modified: String,
-}
-
-fn default_document_type() -> String {
-    "artwork".to_string()
-}
-
-use crate::ToJson;
-impl ToJson for ArtworkData {}
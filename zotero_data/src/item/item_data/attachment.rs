@@ -1,12 +1,137 @@
-use crate::shared_fields::{ItemCommon, Tag};
+use crate::item::Creator;
+use crate::relations::Relations;
+use crate::shared_fields::{FieldError, ItemCommon, ItemFields, Tag, UnknownFields};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
 
-use zotero_derive::ItemCommon;
+use zotero_derive::{ItemCommon, ItemFields};
+
+/// How an attachment is stored, as stored in `linkMode`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum LinkMode {
+    /// A file stored in Zotero.
+    ImportedFile,
+    /// A snapshot of a web page stored in Zotero.
+    ImportedUrl,
+    /// A file on the disk of the user, outside of Zotero storage.
+    LinkedFile,
+    /// A link to a web page.
+    LinkedUrl,
+    /// An image embedded in a note. The parent item is the note.
+    EmbeddedImage,
+    Other(String),
+}
+
+impl LinkMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LinkMode::ImportedFile => "imported_file",
+            LinkMode::ImportedUrl => "imported_url",
+            LinkMode::LinkedFile => "linked_file",
+            LinkMode::LinkedUrl => "linked_url",
+            LinkMode::EmbeddedImage => "embedded_image",
+            LinkMode::Other(s) => s,
+        }
+    }
+
+    /// Whether the file of the attachment is stored in Zotero and has to be uploaded.
+    pub fn is_imported(&self) -> bool {
+        matches!(
+            self,
+            LinkMode::ImportedFile | LinkMode::ImportedUrl | LinkMode::EmbeddedImage
+        )
+    }
+}
+
+impl fmt::Display for LinkMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for LinkMode {
+    fn from(s: &str) -> LinkMode {
+        match s {
+            "imported_file" => LinkMode::ImportedFile,
+            "imported_url" => LinkMode::ImportedUrl,
+            "linked_file" => LinkMode::LinkedFile,
+            "linked_url" => LinkMode::LinkedUrl,
+            "embedded_image" => LinkMode::EmbeddedImage,
+            other => LinkMode::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for LinkMode {
+    fn from(s: String) -> LinkMode {
+        LinkMode::from(s.as_str())
+    }
+}
+
+impl From<LinkMode> for String {
+    fn from(link_mode: LinkMode) -> String {
+        link_mode.as_str().to_string()
+    }
+}
+
+/// MIME types of the files commonly attached in Zotero, by lowercase extension.
+const CONTENT_TYPES: [(&str, &str); 26] = [
+    ("pdf", "application/pdf"),
+    ("epub", "application/epub+zip"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("xhtml", "application/xhtml+xml"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("csv", "text/csv"),
+    ("xml", "application/xml"),
+    ("json", "application/json"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("rtf", "application/rtf"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("svg", "image/svg+xml"),
+    ("webp", "image/webp"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("zip", "application/zip"),
+    ("djvu", "image/vnd.djvu"),
+];
+
+/// The MIME type of a file from its extension, `None` for unknown extensions.
+/// ```rust
+/// use zotero_data::item::content_type_from_path;
+///
+/// assert_eq!(content_type_from_path("Articles/Doe 2020.PDF"), Some("application/pdf"));
+/// ```
+pub fn content_type_from_path<P: AsRef<Path>>(path: P) -> Option<&'static str> {
+    let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+    CONTENT_TYPES
+        .iter()
+        .find(|(e, _)| *e == extension)
+        .map(|(_, content_type)| *content_type)
+}
 
 /// A standalone attachment file (e.g., a PDF, JPEG, DOCX, PPTX, XLSX, or ODT file). Standalone attachment files have limited functionality in Zotero (e.g., they cannot be properly searched or cited). Always attach files to proper Zotero items.
-#[derive(Default, Deserialize, Serialize, Clone, Debug, Builder, ItemCommon)]
+#[derive(Deserialize, Serialize, Clone, Debug, Builder, ItemCommon, ItemFields)]
 #[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
 #[builder(setter(into), default)]
 pub struct AttachmentData {
@@ -16,8 +141,9 @@ pub struct AttachmentData {
     pub charset: String,
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub content_type: String,
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     pub date_added: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub date_modified: Option<String>,
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub filename: String,
@@ -25,28 +151,248 @@ pub struct AttachmentData {
     pub item_type: String,
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub key: String,
-    #[serde(skip_serializing_if = "String::is_empty", default)]
-    pub link_mode: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub link_mode: Option<LinkMode>,
+    /// Only set by Zotero on imported attachments, once the file is uploaded.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub md5: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub mtime: Option<i64>,
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub note: String,
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub parent_item: String,
-    pub relations: HashMap<String, String>,
+    /// Path of a linked file, on the computer of the user.
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub path: String,
+    pub relations: Relations,
+    /// Set when the item is in the trash.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::shared_fields::deleted::is_false",
+        with = "crate::shared_fields::deleted"
+    )]
+    pub deleted: bool,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub collection: Option<Vec<String>>,
+    pub collections: Option<Vec<String>>,
     pub tags: Vec<Tag>,
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub title: String,
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub url: String,
     pub version: usize,
+    #[serde(flatten)]
+    #[builder(setter(skip))]
+    pub unknown_fields: UnknownFields,
 }
 
 fn default_document_type() -> String {
-    "journalArticle".to_string()
+    "attachment".to_string()
+}
+
+impl Default for AttachmentData {
+    fn default() -> Self {
+        AttachmentData {
+            access_date: String::default(),
+            charset: String::default(),
+            content_type: String::default(),
+            date_added: String::default(),
+            date_modified: None,
+            filename: String::default(),
+            item_type: default_document_type(),
+            key: String::default(),
+            link_mode: None,
+            md5: None,
+            mtime: None,
+            note: String::default(),
+            parent_item: String::default(),
+            path: String::default(),
+            relations: Relations::default(),
+            deleted: false,
+            collections: None,
+            tags: Vec::default(),
+            title: String::default(),
+            url: String::default(),
+            version: 0,
+            unknown_fields: UnknownFields::default(),
+        }
+    }
+}
+
+/// Constructors filling the fields Zotero requires for each link mode. The title is the file name or the URL.
+/// ```rust
+/// use zotero_data::item::{AttachmentData, LinkMode};
+///
+/// let pdf = AttachmentData::imported_file("ABCD2345", "Doe 2020.pdf").build().unwrap();
+/// assert_eq!(pdf.link_mode, Some(LinkMode::ImportedFile));
+/// assert_eq!(pdf.content_type, "application/pdf");
+///
+/// let page = AttachmentData::linked_url(None, "https://www.zotero.org")
+///     .title("Zotero")
+///     .build()
+///     .unwrap();
+/// assert_eq!(page.title, "Zotero");
+/// ```
+impl AttachmentData {
+    /// A file to upload to Zotero storage.
+    pub fn imported_file<'a, I: Into<Option<&'a str>>, P: AsRef<Path>>(
+        parent: I,
+        path: P,
+    ) -> AttachmentDataBuilder {
+        let mut builder = file_attachment(parent, LinkMode::ImportedFile, path.as_ref());
+        builder.filename(file_name(path.as_ref()));
+        builder
+    }
+
+    /// A snapshot of `url` saved in `path`, to upload to Zotero storage.
+    pub fn imported_url<'a, I: Into<Option<&'a str>>, P: AsRef<Path>>(
+        parent: I,
+        url: &str,
+        path: P,
+    ) -> AttachmentDataBuilder {
+        let mut builder = file_attachment(parent, LinkMode::ImportedUrl, path.as_ref());
+        builder.filename(file_name(path.as_ref())).url(url);
+        if builder
+            .content_type
+            .as_deref()
+            .unwrap_or_default()
+            .is_empty()
+        {
+            builder.content_type("text/html");
+        }
+        builder
+    }
+
+    /// A link to a file outside of Zotero storage.
+    pub fn linked_file<'a, I: Into<Option<&'a str>>, P: AsRef<Path>>(
+        parent: I,
+        path: P,
+    ) -> AttachmentDataBuilder {
+        let mut builder = file_attachment(parent, LinkMode::LinkedFile, path.as_ref());
+        builder.path(path.as_ref().to_string_lossy());
+        builder
+    }
+
+    /// A link to a web page.
+    pub fn linked_url<'a, I: Into<Option<&'a str>>>(parent: I, url: &str) -> AttachmentDataBuilder {
+        let mut builder = AttachmentDataBuilder::default();
+        builder
+            .link_mode(LinkMode::LinkedUrl)
+            .parent_item(parent.into().unwrap_or_default())
+            .title(url)
+            .url(url);
+        builder
+    }
+
+    /// An image embedded in the note `parent_note`.
+    pub fn embedded_image<P: AsRef<Path>>(parent_note: &str, path: P) -> AttachmentDataBuilder {
+        let mut builder = file_attachment(parent_note, LinkMode::EmbeddedImage, path.as_ref());
+        builder.filename(file_name(path.as_ref())).title("");
+        builder
+    }
+}
+
+fn file_attachment<'a, I: Into<Option<&'a str>>>(
+    parent: I,
+    link_mode: LinkMode,
+    path: &Path,
+) -> AttachmentDataBuilder {
+    let mut builder = AttachmentDataBuilder::default();
+    builder
+        .link_mode(link_mode)
+        .parent_item(parent.into().unwrap_or_default())
+        .title(file_name(path))
+        .content_type(content_type_from_path(path).unwrap_or_default());
+    builder
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
 }
 
 use crate::ToJson;
 impl ToJson for AttachmentData {}
+
+#[cfg(test)]
+mod test_attachment {
+    use super::*;
+    use crate::item::ItemType;
+
+    #[test]
+    fn constructors() {
+        let file = AttachmentData::imported_file("ABCD2345", "papers/Doe 2020.pdf")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&file).unwrap(),
+            serde_json::json!({
+                "itemType": "attachment",
+                "linkMode": "imported_file",
+                "parentItem": "ABCD2345",
+                "title": "Doe 2020.pdf",
+                "filename": "Doe 2020.pdf",
+                "contentType": "application/pdf",
+                "tags": [],
+                "relations": {},
+                "version": 0
+            })
+        );
+
+        let snapshot = AttachmentData::imported_url(None, "https://www.zotero.org", "index")
+            .build()
+            .unwrap();
+        assert_eq!(snapshot.content_type, "text/html");
+        assert_eq!(snapshot.url, "https://www.zotero.org");
+        assert!(snapshot.parent_item.is_empty());
+
+        let linked = AttachmentData::linked_file("ABCD2345", "/home/john/Doe.epub")
+            .build()
+            .unwrap();
+        assert_eq!(linked.path, "/home/john/Doe.epub");
+        assert_eq!(linked.content_type, "application/epub+zip");
+        assert!(linked.filename.is_empty());
+
+        let link = AttachmentData::linked_url("ABCD2345", "https://www.zotero.org")
+            .build()
+            .unwrap();
+        assert_eq!(link.link_mode, Some(LinkMode::LinkedUrl));
+        assert_eq!(link.title, "https://www.zotero.org");
+
+        for attachment in [file, snapshot, linked, link] {
+            assert!(ItemType::Attachment(attachment).is_valid());
+        }
+    }
+
+    #[test]
+    fn link_modes() {
+        let attachment: AttachmentData = serde_json::from_value(serde_json::json!({
+            "linkMode": "linked_url",
+            "dateAdded": "",
+            "tags": [],
+            "relations": {},
+            "version": 1
+        }))
+        .unwrap();
+        assert_eq!(attachment.link_mode, Some(LinkMode::LinkedUrl));
+        assert!(!LinkMode::LinkedUrl.is_imported());
+
+        let attachment: AttachmentData = serde_json::from_value(serde_json::json!({
+            "linkMode": "cloud_file",
+            "dateAdded": "",
+            "tags": [],
+            "relations": {},
+            "version": 1
+        }))
+        .unwrap();
+        assert_eq!(
+            attachment.link_mode,
+            Some(LinkMode::Other("cloud_file".to_string()))
+        );
+        assert_eq!(
+            serde_json::to_value(&attachment).unwrap()["linkMode"],
+            "cloud_file"
+        );
+    }
+}
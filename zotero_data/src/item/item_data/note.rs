@@ -1,25 +1,26 @@
-use crate::shared_fields::{ItemCommon, Tag};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::item::Creator;
+use crate::note;
+use crate::relations::Relations;
+use crate::shared_fields::{FieldError, ItemCommon, ItemFields, Tag, UnknownFields};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use derive_builder::Builder;
-use zotero_derive::ItemCommon;
-
-fn note_title() -> String {
-    "Note".to_string()
-}
+use zotero_derive::{ItemCommon, ItemFields};
 
 /// A standalone note. Notes can be used for organizing and annotating in Zotero. If you cite a standalone note, Zotero will use the first 120 characters as the item title (and will treat the note as an author-less and date-less item). Citing notes is not a reliable way to add standalone commentary to a bibliography or reference list.
-#[derive(Default, Deserialize, Serialize, Clone, Debug, Builder, ItemCommon)]
+///
+/// The title is not stored by Zotero, it is derived from the first line of the note.
+#[derive(Deserialize, Serialize, Clone, Debug, Builder, ItemCommon, ItemFields)]
+#[serde(remote = "Self")]
 #[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
-#[builder(setter(into), default)]
+#[builder(setter(into), default, build_fn(private, name = "build_without_title"))]
 pub struct NoteData {
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub key: String,
     #[builder(setter(skip))]
     pub version: usize,
     #[builder(setter(skip))]
-    #[serde(skip_serializing, default = "note_title")]
+    #[serde(skip_serializing, default)]
     pub title: String,
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub parent_item: String,
@@ -29,16 +30,100 @@ pub struct NoteData {
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub note: String,
     pub tags: Vec<Tag>,
-    pub relations: HashMap<String, String>,
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub collections: Option<Vec<String>>,
+    pub relations: Relations,
+    /// Set when the item is in the trash.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::shared_fields::deleted::is_false",
+        with = "crate::shared_fields::deleted"
+    )]
+    pub deleted: bool,
+    #[serde(skip_serializing, default)]
     pub date_added: String,
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     pub date_modified: String,
+    #[serde(flatten)]
+    #[builder(setter(skip))]
+    pub unknown_fields: UnknownFields,
 }
 
 fn default_document_type() -> String {
     "note".to_string()
 }
 
+impl Default for NoteData {
+    fn default() -> Self {
+        NoteData {
+            key: String::default(),
+            version: 0,
+            title: String::default(),
+            parent_item: String::default(),
+            item_type: default_document_type(),
+            note: String::default(),
+            tags: Vec::default(),
+            collections: None,
+            relations: Relations::default(),
+            deleted: false,
+            date_added: String::default(),
+            date_modified: String::default(),
+            unknown_fields: UnknownFields::default(),
+        }
+    }
+}
+
+impl NoteData {
+    /// A note written in Markdown, converted to note HTML.
+    pub fn from_markdown(markdown: &str) -> NoteData {
+        let mut note = NoteData::default();
+        note.set_markdown(markdown);
+        note
+    }
+
+    /// Replace the HTML of the note, updating its title.
+    pub fn set_note<S: Into<String>>(&mut self, html: S) {
+        self.note = html.into();
+        self.title = note::note_title(&self.note);
+    }
+
+    /// Replace the content of the note with Markdown converted to note HTML.
+    pub fn set_markdown(&mut self, markdown: &str) {
+        self.set_note(note::markdown_to_html(markdown));
+    }
+
+    /// The note converted to Markdown.
+    pub fn markdown(&self) -> String {
+        note::html_to_markdown(&self.note)
+    }
+
+    /// The text of the note, without markup.
+    pub fn text(&self) -> String {
+        note::html_to_text(&self.note)
+    }
+}
+
+impl NoteDataBuilder {
+    pub fn build(&self) -> Result<NoteData, NoteDataBuilderError> {
+        let mut note = self.build_without_title()?;
+        note.title = note::note_title(&note.note);
+        Ok(note)
+    }
+}
+
+impl<'de> Deserialize<'de> for NoteData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut note = NoteData::deserialize(deserializer)?;
+        note.title = note::note_title(&note.note);
+        Ok(note)
+    }
+}
+
+impl Serialize for NoteData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        NoteData::serialize(self, serializer)
+    }
+}
+
 use crate::ToJson;
 impl ToJson for NoteData {}
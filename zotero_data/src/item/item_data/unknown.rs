@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::item::Creator;
+use crate::shared_fields::{FieldError, ItemCommon, ItemFields, Tag, UnknownFields};
+
+/// Data of an item type unknown to this crate, e.g. an item type added to Zotero after this release.
+///
+/// Creators, tags and collections are parsed so that they can be read and modified like those of any other
+/// item type. Every other field is kept as raw JSON in `fields` and written back unchanged.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct UnknownItemData {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub creators: Vec<Creator>,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collections: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub fields: UnknownFields,
+}
+
+impl UnknownItemData {
+    /// The `itemType` of the item.
+    pub fn item_type(&self) -> &str {
+        self.get_field("itemType").unwrap_or("")
+    }
+
+    /// A field as raw JSON.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.fields.get(name)
+    }
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut String> {
+        match self.fields.get_mut(name) {
+            Some(Value::String(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl ItemCommon for UnknownItemData {
+    fn title(&self) -> &str {
+        self.get_field("title").unwrap_or("")
+    }
+
+    fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    fn key(&self) -> &str {
+        self.get_field("key").unwrap_or("")
+    }
+}
+
+/// Text fields are read from the raw JSON, and only exist when the item has them.
+impl ItemFields for UnknownItemData {
+    fn get_field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).and_then(Value::as_str)
+    }
+
+    fn set_field<S: Into<String>>(&mut self, name: &str, value: S) -> Result<(), FieldError> {
+        match name {
+            "creators" | "tags" | "collections" => Err(FieldError::UnknownField(name.to_string())),
+            _ => {
+                self.fields
+                    .insert(name.to_string(), Value::String(value.into()));
+                Ok(())
+            }
+        }
+    }
+
+    fn fields(&self) -> std::vec::IntoIter<(&str, &str)> {
+        self.fields
+            .iter()
+            .filter(|(name, _)| {
+                !["key", "itemType", "dateAdded", "dateModified"].contains(&name.as_str())
+            })
+            .filter_map(|(name, value)| Some((name.as_str(), value.as_str()?)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn creators(&self) -> &[Creator] {
+        &self.creators
+    }
+
+    fn creators_mut(&mut self) -> Option<&mut Vec<Creator>> {
+        Some(&mut self.creators)
+    }
+
+    fn collections(&self) -> &[String] {
+        self.collections.as_deref().unwrap_or_default()
+    }
+
+    fn collections_mut(&mut self) -> Option<&mut Vec<String>> {
+        Some(self.collections.get_or_insert_with(Vec::new))
+    }
+
+    fn date(&self) -> Option<&str> {
+        self.get_field("date")
+    }
+
+    fn date_mut(&mut self) -> Option<&mut String> {
+        self.get_mut("date")
+    }
+
+    fn url(&self) -> Option<&str> {
+        self.get_field("url")
+    }
+
+    fn url_mut(&mut self) -> Option<&mut String> {
+        self.get_mut("url")
+    }
+
+    fn doi(&self) -> Option<&str> {
+        self.get_field("DOI")
+    }
+
+    fn doi_mut(&mut self) -> Option<&mut String> {
+        self.get_mut("DOI")
+    }
+
+    fn abstract_note(&self) -> Option<&str> {
+        self.get_field("abstractNote")
+    }
+
+    fn abstract_note_mut(&mut self) -> Option<&mut String> {
+        self.get_mut("abstractNote")
+    }
+
+    fn language(&self) -> Option<&str> {
+        self.get_field("language")
+    }
+
+    fn language_mut(&mut self) -> Option<&mut String> {
+        self.get_mut("language")
+    }
+
+    fn extra(&self) -> Option<&str> {
+        self.get_field("extra")
+    }
+
+    fn extra_mut(&mut self) -> Option<&mut String> {
+        self.get_mut("extra")
+    }
+
+    fn parent_item(&self) -> Option<&str> {
+        self.get_field("parentItem")
+    }
+
+    fn parent_item_mut(&mut self) -> Option<&mut String> {
+        self.get_mut("parentItem")
+    }
+}
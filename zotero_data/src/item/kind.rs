@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::item::CreatorType;
+
+include!(concat!(env!("OUT_DIR"), "/item_type_kind.rs"));
+
+impl ItemTypeKind {
+    /// Zotero name of the field holding the title of this item kind.
+    /// Notes, attachments and annotations do not need a title.
+    pub fn title_field(&self) -> Option<&'static str> {
+        match self {
+            ItemTypeKind::Annotation | ItemTypeKind::Attachment | ItemTypeKind::Note => None,
+            _ => self.field_for_base("title"),
+        }
+    }
+
+    /// The creator type Zotero uses by default for this item kind.
+    pub fn primary_creator_type(&self) -> Option<&'static CreatorType> {
+        self.creator_types().first()
+    }
+
+    /// Check whether a creator type is legal for this item kind.
+    pub fn is_valid_creator_type(&self, creator_type: &CreatorType) -> bool {
+        self.creator_types().contains(creator_type)
+    }
+}
+
+impl fmt::Display for ItemTypeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ItemTypeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ItemTypeKind::ALL
+            .iter()
+            .find(|kind| kind.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("unknown item type \"{}\"", s))
+    }
+}
+
+#[cfg(test)]
+mod test_item_type_kind {
+    use super::*;
+
+    #[test]
+    fn base_field_mappings() {
+        assert_eq!(ItemTypeKind::Case.base_field("caseName"), Some("title"));
+        assert_eq!(
+            ItemTypeKind::Case.field_for_base("date"),
+            Some("dateDecided")
+        );
+        assert_eq!(
+            ItemTypeKind::Report.field_for_base("publisher"),
+            Some("institution")
+        );
+        assert_eq!(
+            ItemTypeKind::Book.field_for_base("publisher"),
+            Some("publisher")
+        );
+        assert_eq!(ItemTypeKind::Podcast.field_for_base("date"), None);
+        assert_eq!(ItemTypeKind::Statute.title_field(), Some("nameOfAct"));
+        assert_eq!(ItemTypeKind::Note.title_field(), None);
+    }
+
+    #[test]
+    fn date_fields() {
+        assert!(ItemTypeKind::Patent.is_date_field("issueDate"));
+        assert!(ItemTypeKind::Patent.is_date_field("filingDate"));
+        assert!(!ItemTypeKind::Patent.is_date_field("accessDate"));
+    }
+
+    #[test]
+    fn item_type_names() {
+        for kind in ItemTypeKind::ALL {
+            assert_eq!(kind.as_str().parse::<ItemTypeKind>(), Ok(kind));
+        }
+        assert!("unknownType".parse::<ItemTypeKind>().is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Three-way merge of concurrent edits of an item.
+//!
+//! When a write is rejected with `412 Precondition Failed`, the item has been modified on the server since it was
+//! read. [`merge`] combines the local edit with the server version, using the version both started from.
+//!
+//! ```rust
+//! use zotero_data::item::{merge, BookDataBuilder, ItemType};
+//! use zotero_data::shared_fields::ItemFields;
+//!
+//! let base = ItemType::Book(BookDataBuilder::default().title("Lorem").build().unwrap());
+//!
+//! let mut local = base.clone();
+//! local.set_field("publisher", "Doe editions").unwrap();
+//!
+//! let mut remote = base.clone();
+//! remote.set_field("place", "Paris").unwrap();
+//!
+//! let result = merge(&base, &local, &remote).unwrap();
+//! assert!(result.is_clean());
+//! assert_eq!(result.merged.get_field("publisher"), Some("Doe editions"));
+//! assert_eq!(result.merged.get_field("place"), Some("Paris"));
+//! ```
+
+use serde_json::{Map, Value};
+use std::fmt;
+
+use crate::item::diff::{as_object, empty_like, IGNORED_FIELDS};
+use crate::item::ItemType;
+
+/// Fields merged as sets: additions and removals from both sides are kept.
+const SET_FIELDS: [&str; 2] = ["tags", "collections"];
+
+/// A field changed differently on both sides, with its Zotero name and its three JSON values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MergeConflict {
+    pub field: String,
+    pub base: Value,
+    pub local: Value,
+    pub remote: Value,
+}
+
+/// Error returned by [`merge`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeError {
+    /// The merged fields don't make a valid item, e.g. when the local edit changed the item type.
+    InvalidItem(String),
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::InvalidItem(err) => write!(f, "the merged item is not valid: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+/// The result of [`merge`].
+#[derive(Clone, Debug)]
+pub struct MergeResult {
+    /// The merged item. Conflicting fields keep their remote value.
+    pub merged: ItemType,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    /// Returns `true` if every change could be merged automatically.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Merge a local edit and a remote edit of the same item, both made from `base`.
+///
+/// Fields changed on one side only take the changed value, tags and collections are merged as sets and fields
+/// changed differently on both sides are reported as conflicts. The key, version and dates come from `remote`,
+/// so that the merged item can be written back against the current server version.
+///
+/// Fails if the merged fields can't be read back as an item: nothing is merged silently.
+pub fn merge(
+    base: &ItemType,
+    local: &ItemType,
+    remote: &ItemType,
+) -> Result<MergeResult, MergeError> {
+    let base = as_object(base);
+    let local = as_object(local);
+    let remote_object = as_object(remote);
+
+    let mut merged = remote_object.clone();
+    let mut conflicts = Vec::new();
+
+    let mut fields: Vec<&String> = local.keys().collect();
+    fields.extend(base.keys().filter(|f| !local.contains_key(*f)));
+
+    for field in fields
+        .into_iter()
+        .filter(|f| !IGNORED_FIELDS.contains(&f.as_str()))
+    {
+        let local_value = value_or_empty(&local, field, &base);
+        let base_value = value_or_empty(&base, field, &local);
+        let remote_value = value_or_empty(&remote_object, field, &local);
+
+        if local_value == base_value || local_value == remote_value {
+            continue;
+        }
+        if remote_value == base_value {
+            merged.insert(field.clone(), local_value);
+        } else if SET_FIELDS.contains(&field.as_str()) {
+            merged.insert(
+                field.clone(),
+                merge_sets(&base_value, &local_value, &remote_value),
+            );
+        } else {
+            conflicts.push(MergeConflict {
+                field: field.clone(),
+                base: base_value,
+                local: local_value,
+                remote: remote_value,
+            });
+        }
+    }
+
+    Ok(MergeResult {
+        merged: serde_json::from_value(Value::Object(merged))
+            .map_err(|err| MergeError::InvalidItem(err.to_string()))?,
+        conflicts,
+    })
+}
+
+/// The value of a field, or the empty value of the same kind as in `other` if it was left out of the serialization.
+fn value_or_empty(object: &Map<String, Value>, field: &str, other: &Map<String, Value>) -> Value {
+    match (object.get(field), other.get(field)) {
+        (Some(value), _) => value.clone(),
+        (None, Some(other)) => empty_like(other),
+        (None, None) => Value::Null,
+    }
+}
+
+/// Apply the additions and removals made locally since `base` to `remote`.
+fn merge_sets(base: &Value, local: &Value, remote: &Value) -> Value {
+    let as_list = |value: &Value| match value {
+        Value::Array(list) => list.clone(),
+        _ => Vec::new(),
+    };
+    let base = as_list(base);
+    let local = as_list(local);
+    let mut merged = as_list(remote);
+
+    merged.retain(|v| local.contains(v) || !base.contains(v));
+    for value in local {
+        if !base.contains(&value) && !merged.contains(&value) {
+            merged.push(value);
+        }
+    }
+    Value::Array(merged)
+}
+
+#[cfg(test)]
+mod test_merge {
+    use super::*;
+    use crate::item::{Creator, JournalArticleData, JournalArticleDataBuilder};
+    use crate::shared_fields::{ItemCommon, ItemFields, Tag};
+
+    fn tag(tag: &str) -> Tag {
+        Tag {
+            tag: tag.to_string(),
+        }
+    }
+
+    fn article() -> JournalArticleData {
+        let mut article = JournalArticleDataBuilder::default()
+            .title("Lorem")
+            .tags(vec![tag("history"), tag("art")])
+            .collections(Some(vec!["AAAAAAAA".to_string()]))
+            .build()
+            .unwrap();
+        article.key = "ABCD2345".to_string();
+        article.version = 12;
+        article
+    }
+
+    #[test]
+    fn non_overlapping_changes() {
+        let base = article();
+
+        let mut local = article();
+        local.volume = "4".to_string();
+        local.tags.retain(|t| t.tag != "art");
+        local.tags.push(tag("philosophy"));
+        local
+            .creators
+            .push(Creator::two_field("author", "John", "Doe"));
+
+        let mut remote = article();
+        remote.version = 13;
+        remote.pages = "1-10".to_string();
+        remote.tags.push(tag("music"));
+        remote.collections = Some(vec!["AAAAAAAA".to_string(), "BBBBBBBB".to_string()]);
+
+        let result = merge(
+            &ItemType::JournalArticle(base),
+            &ItemType::JournalArticle(local),
+            &ItemType::JournalArticle(remote),
+        )
+        .unwrap();
+        assert!(result.is_clean());
+
+        let ItemType::JournalArticle(merged) = &result.merged else {
+            panic!("expected a journal article, got {:?}", result.merged);
+        };
+        assert_eq!(merged.version, 13);
+        assert_eq!(merged.volume, "4");
+        assert_eq!(merged.pages, "1-10");
+        assert_eq!(merged.creators.len(), 1);
+        assert_eq!(
+            merged
+                .tags
+                .iter()
+                .map(|t| t.tag.as_str())
+                .collect::<Vec<_>>(),
+            vec!["history", "music", "philosophy"]
+        );
+        assert_eq!(result.merged.collections(), ["AAAAAAAA", "BBBBBBBB"]);
+    }
+
+    #[test]
+    fn conflicting_changes() {
+        let base = article();
+
+        let mut local = article();
+        local.title = "Local title".to_string();
+        local.issue = "2".to_string();
+
+        let mut remote = article();
+        remote.title = "Remote title".to_string();
+        remote.issue = "2".to_string();
+
+        let result = merge(
+            &ItemType::JournalArticle(base),
+            &ItemType::JournalArticle(local),
+            &ItemType::JournalArticle(remote),
+        )
+        .unwrap();
+        assert_eq!(
+            result.conflicts,
+            vec![MergeConflict {
+                field: "title".to_string(),
+                base: Value::from("Lorem"),
+                local: Value::from("Local title"),
+                remote: Value::from("Remote title"),
+            }]
+        );
+        assert_eq!(result.merged.title(), "Remote title");
+        assert_eq!(result.merged.get_field("issue"), Some("2"));
+    }
+
+    #[test]
+    fn invalid_merged_item() {
+        let attachment = ItemType::Attachment(
+            crate::item::AttachmentDataBuilder::default()
+                .title("Lorem")
+                .build()
+                .unwrap(),
+        );
+        // an item type the merged fields can't be read back as
+        let local = ItemType::Unknown(
+            serde_json::from_value(serde_json::json!({
+                "itemType": "attachment",
+                "title": "Lorem",
+                "mtime": "yesterday"
+            }))
+            .unwrap(),
+        );
+
+        assert!(matches!(
+            merge(&attachment, &local, &attachment),
+            Err(MergeError::InvalidItem(_))
+        ));
+    }
+}
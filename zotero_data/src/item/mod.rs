@@ -18,16 +18,28 @@
 //! let new_book : BookData = BookDataBuilder::default()
 //!       .title("Book title")
 //!       .creators(creators)
-//!       .item_type("book")
 //!       .build()
 //!       .unwrap();
 //! ```
 
+mod convert;
+mod creator;
+mod date;
+mod diff;
+mod duplicates;
 mod item_data;
+mod kind;
+mod merge;
+mod tree;
+mod validation;
 
-use chrono::DateTime;
-use chrono::NaiveTime;
-use chrono::{Local, NaiveDate, NaiveDateTime};
+pub use convert::DroppedField;
+pub use creator::{Creator, CreatorBuilder, CreatorBuilderError, CreatorType};
+pub use date::{DatePrecision, Season, ZoteroDate};
+pub use diff::{diff, FieldChange, ItemPatch};
+pub use duplicates::{find_duplicates, DuplicateCluster, MatchReason};
+
+pub use item_data::content_type_from_path;
 pub use item_data::ArtworkData;
 pub use item_data::ArtworkDataBuilder;
 pub use item_data::AttachmentData;
@@ -48,6 +60,8 @@ pub use item_data::ComputerProgramData;
 pub use item_data::ComputerProgramDataBuilder;
 pub use item_data::ConferencePaperData;
 pub use item_data::ConferencePaperDataBuilder;
+pub use item_data::DatasetData;
+pub use item_data::DatasetDataBuilder;
 pub use item_data::DictionaryEntryData;
 pub use item_data::DictionaryEntryDataBuilder;
 pub use item_data::DocumentData;
@@ -70,6 +84,7 @@ pub use item_data::JournalArticleData;
 pub use item_data::JournalArticleDataBuilder;
 pub use item_data::LetterData;
 pub use item_data::LetterDataBuilder;
+pub use item_data::LinkMode;
 pub use item_data::MagazineArticleData;
 pub use item_data::MagazineArticleDataBuilder;
 pub use item_data::ManuscriptData;
@@ -84,110 +99,60 @@ pub use item_data::PatentData;
 pub use item_data::PatentDataBuilder;
 pub use item_data::PodcastData;
 pub use item_data::PodcastDataBuilder;
+pub use item_data::PreprintData;
+pub use item_data::PreprintDataBuilder;
 pub use item_data::PresentationData;
 pub use item_data::PresentationDataBuilder;
 pub use item_data::RadioBroadcastData;
 pub use item_data::RadioBroadcastDataBuilder;
 pub use item_data::ReportData;
 pub use item_data::ReportDataBuilder;
+pub use item_data::StandardData;
+pub use item_data::StandardDataBuilder;
 pub use item_data::StatuteData;
 pub use item_data::StatuteDataBuilder;
 pub use item_data::ThesisData;
 pub use item_data::ThesisDataBuilder;
 pub use item_data::TvBroadcastData;
 pub use item_data::TvBroadcastDataBuilder;
+pub use item_data::UnknownItemData;
 pub use item_data::VideoRecordingData;
 pub use item_data::VideoRecordingDataBuilder;
 pub use item_data::WebpageData;
 pub use item_data::WebpageDataBuilder;
-use once_cell::sync::Lazy;
-use regex::Regex;
+pub use item_data::{
+    annotations_to_html, annotations_to_markdown, AnnotationData, AnnotationDataBuilder,
+    AnnotationPosition, AnnotationType,
+};
+pub use kind::ItemTypeKind;
+pub use merge::{merge, MergeConflict, MergeError, MergeResult};
+pub use tree::{ItemNode, ItemTree, ItemTreeError};
+pub use validation::{Diagnostic, DiagnosticKind, Severity};
 
 use serde::Deserialize;
 use serde::Serialize;
 
-use crate::shared_fields::{ItemCommon, Library, Links, Tag};
-
-use derive_builder::Builder;
-
-use zotero_derive::ItemCommon;
-
-#[derive(Deserialize, Serialize, Debug, Clone, ItemCommon)]
-#[serde(rename_all = "camelCase", tag = "itemType")]
-/// An enum that holds structs used to deserialize zotero item data into rust structs.
-pub enum ItemType {
-    /// A piece of artwork (e.g., an oil painting, photograph, or sculpture). Also use this item type for other types of images or visual items (e.g., scientific figures).
-    Artwork(ArtworkData),
-    /// Any form of audio recording, including music, spoken word, sound effects, archival recordings, or audio-based scientific figures.
-    AudioRecording(AudioRecordingData),
-    /// A proposed piece of legislation.
-    Bill(BillData),
-    /// An article or entry posted to a personal blog website. For online articles published as part of a larger online publication (e.g., NYT BlogsData), using Magazine Article or Newspaper Article generally yields better results
-    BlogPost(BlogPostData),
-    /// A book or similar published item. For government documents, technical reports, manuals, etc., use Report instead. This item type can also be adapted to fit many types of unusual items.
-    Book(BookData),
-    /// A section of a book. Usually chapters, but also forewords, prefaces, introductions, appendices, afterwords, comments, etc.
-    BookSection(BookSectionData),
-    /// A legal case, either published or unpublished.
-    Case(CaseData),
-    /// A piece of software or other computer program.
-    ComputerProgram(ComputerProgramData),
-    /// A paper presented at a conference and subsequently published in a formal conference proceedings publication (e.g., as a book, report, or issue of a journal). For conference papers that have not been published in a proceedings, use Presentation.
-    ConferencePaper(ConferencePaperData),
-    /// An entry published as part of a dictionary.
-    DictionaryEntry(DictionaryEntryData),
-    /// A generic document item. This item type has a poor selection of fields and poor support in citation styles, so it should generally be avoided.
-    Document(DocumentData),
-    /// A message sent via email. This type could also be used for other forms of personal communication.
-    Email(EmailData),
-    /// An article or chapter published as part of an encyclopedia.
-    EncyclopediaArticle(EncyclopediaArticleData),
-    ///  A film or motion picture. Generally, use this type for artistically-oriented films (including fictional, non-fictional, and documentary films). For other types of video items, use Video Recording.
-    Film(FilmData),
-    /// A post on an online discussion forum. Also use this type for items such as Facebook posts or tweets.
-    ForumPost(ForumPostData),
-    /// A formal hearing or meeting report by a legislative body.
-    Hearing(HearingData),
-    /// A message sent via an instant message or chat service. This type could also be used for other forms of personal communication.
-    InstantMessage(InstantMessageData),
-    /// An interview with a person, including recordings, transcripts, or other records of the interview.
-    Interview(InterviewData),
-    /// An article published in a scholarly journal (either print or online).
-    JournalArticle(JournalArticleData),
-    /// A letter sent between persons or organizations. This type could also be used for other forms of personal communication.
-    Letter(LetterData),
-    /// An article published in a non-scholarly, popular, or trade magazine (either print or online).
-    MagazineArticle(MagazineArticleData),
-    /// An unpublished manuscript. Use this type for both historical documents and modern unpublished work (e.g., unpublished manuscripts, manuscripts submitted for publication, working papers that are not widely available). Can also be used for other forms of historical or archival documents. This item type can also be adapted to fit many types of unusual items.
-    Manuscript(ManuscriptData),
-    /// A map. Also use this type for geographic models.
-    Map(MapData),
-    /// An article published in a newspaper (either print or online).
-    NewspaperArticle(NewspaperArticleData),
-    /// A patent awarded for an invention.
-    Patent(PatentData),
-    /// A podcast (an episode of an audio or video program distributed online, often via subscription).
-    Podcast(PodcastData),
-    /// A presentation made as part of a conference, meeting, symposium, lecture, etc. This item type refers to the presentation itself, not a written version published as part of a conference proceedings (use Conference Paper for such published versions).
-    Presentation(PresentationData),
-    /// An audio broadcast, such as a radio news show, an episode of a radio entertainment series, or similar. Includes broadcasts from online radio stations and audio broadcasts archived online (cf. Podcast).
-    RadioBroadcast(RadioBroadcastData),
-    /// A report published by an organization, institution, government department, or similar entity. Also used for working papers and preprints distributed through institutional repositories or preprint servers. This item type can also be adapted to fit many types of unusual items.
-    Report(ReportData),
-    /// A law or other piece of enacted legislation.
-    Statute(StatuteData),
-    /// A thesis submitted as part of a student applying for a degree (either published or unpublished).
-    Thesis(ThesisData),
-    /// An episode of a television series.
-    TvBroadcast(TvBroadcastData),
-    /// A video recording. Use this type for general video items that do not fit into one of the more specific video item types (e.g., Film, TV BroadcastData), such as YouTube videos or video-based scientific figures.
-    VideoRecording(VideoRecordingData),
-    /// An online page of a website. When possible, use one of the more specific item types above (e.g., Magazine Article, Blog Post, Report).
-    Webpage(WebpageData),
-    /// A standalone attachment file (e.g., a PDF, JPEG, DOCX, PPTX, XLSX, or ODT file). Standalone attachment files have limited functionality in Zotero (e.g., they cannot be properly searched or cited). Always attach files to proper Zotero items.
-    Attachment(AttachmentData),
-    /// A standalone note. Notes can be used for organizing and annotating in Zotero. If you cite a standalone note, Zotero will use the first 120 characters as the item title (and will treat the note as an author-less and date-less item). Citing notes is not a reliable way to add standalone commentary to a bibliography or reference list.
-    Note(NoteData),
+use crate::relations::{ItemUri, LibraryKind, Relations};
+use crate::shared_fields::{
+    FieldError, ItemCommon, ItemFields, Library, Links, Tag, UnknownFields,
+};
+
+use zotero_derive::{ItemCommon, ItemFields};
+
+include!(concat!(env!("OUT_DIR"), "/item_type.rs"));
+
+impl ItemType {
+    /// Creators whose creator type is not legal for this item type.
+    pub fn invalid_creators(&self) -> Vec<&Creator> {
+        match self.kind() {
+            Some(kind) => self
+                .creators()
+                .iter()
+                .filter(|c| !kind.is_valid_creator_type(c.creator_type()))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
 }
 
 /// A struct used to represent or deserialize zotero items into rust struct
@@ -199,6 +164,8 @@ pub struct Item {
     pub links: Links,
     pub meta: ItemMeta,
     pub data: ItemType,
+    #[serde(flatten)]
+    pub unknown_fields: UnknownFields,
 }
 
 impl Item {
@@ -218,321 +185,49 @@ impl Item {
         self.tags().iter().any(|t| t.tag == tag)
     }
 
-    //author function can not be implement for all structs automatically, fields do not exists everywhere
-    pub fn author(&self) -> String {
-        match &self.data {
-            ItemType::Artwork(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Book(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Document(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::JournalArticle(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Report(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::MagazineArticle(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Map(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Letter(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Statute(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::EncyclopediaArticle(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Bill(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Case(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Hearing(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::ConferencePaper(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::ForumPost(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Webpage(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::NewspaperArticle(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::RadioBroadcast(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::InstantMessage(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::DictionaryEntry(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Presentation(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Manuscript(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::BlogPost(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::TvBroadcast(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Patent(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Email(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::VideoRecording(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Thesis(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::ComputerProgram(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Podcast(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::AudioRecording(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::BookSection(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Interview(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Film(d) => d
-                .creators
-                .iter()
-                .map(|c| c.full_name())
-                .collect::<Vec<String>>()
-                .join(", "),
-            ItemType::Attachment(_) => {
-                //Attachments are mostly Pdf with parents, for the author you need to call author() on the parent!
-                "".to_string()
-            }
-            ItemType::Note(_) => "You".to_string(),
-        }
+    /// The URI identifying this item in relations. Returns `None` if the library type is unknown.
+    pub fn uri(&self) -> Option<ItemUri> {
+        let library_kind = LibraryKind::from_library_type(&self.library.r#type)?;
+        Some(ItemUri::new(library_kind, self.library.id, &self.key))
     }
 
-    //author function can not be implement for all structs automatically, fields do not exists everywhere
-    pub fn date(&self) -> DateTime<Local> {
-        let date_str = match &self.data {
-            ItemType::Artwork(d) => &d.date,
-            ItemType::Book(d) => &d.date,
-            ItemType::Document(d) => &d.date,
-            ItemType::JournalArticle(d) => &d.date,
-            ItemType::Report(d) => &d.date,
-            ItemType::MagazineArticle(d) => &d.date,
-            ItemType::Map(d) => &d.date,
-            ItemType::Letter(d) => &d.date,
-            ItemType::Statute(d) => &d.date_enacted,
-            ItemType::EncyclopediaArticle(d) => &d.date,
-            ItemType::Bill(d) => &d.date,
-            ItemType::Case(d) => &d.date_decided,
-            ItemType::Hearing(d) => &d.date,
-            ItemType::ConferencePaper(d) => &d.date,
-            ItemType::ForumPost(d) => &d.date,
-            ItemType::Webpage(d) => &d.date,
-            ItemType::NewspaperArticle(d) => &d.date,
-            ItemType::RadioBroadcast(d) => &d.date,
-            ItemType::InstantMessage(d) => &d.date,
-            ItemType::DictionaryEntry(d) => &d.date,
-            ItemType::Presentation(d) => &d.date,
-            ItemType::Manuscript(d) => &d.date,
-            ItemType::BlogPost(d) => &d.date,
-            ItemType::TvBroadcast(d) => &d.date,
-            ItemType::Patent(d) => &d.issue_date,
-            ItemType::Email(d) => &d.date,
-            ItemType::VideoRecording(d) => &d.date,
-            ItemType::Thesis(d) => &d.date,
-            ItemType::ComputerProgram(d) => &d.date,
-            ItemType::Podcast(d) => &d.access_date,
-            ItemType::AudioRecording(d) => &d.date,
-            ItemType::BookSection(d) => &d.access_date,
-            ItemType::Interview(d) => &d.date,
-            ItemType::Film(d) => &d.date,
-            ItemType::Attachment(d) => &d.date_added,
-            ItemType::Note(d) => &d.date_added,
-        };
-        convert_zotero_date_str(date_str)
+    pub fn relations(&self) -> Relations {
+        self.data.relations()
     }
-}
-
-static DATE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d{4})-(\d{2})-(\d{2})").unwrap());
-
-static FORMATTER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d{2})/(\d{4})").unwrap());
-
-fn convert_zotero_date_str(date_str: &str) -> DateTime<Local> {
-    let date_captures = DATE_REGEX.captures(date_str);
-
-    let formatter_captures = FORMATTER_REGEX.captures(date_str);
 
-    let expanded_date = if let Some(captures) = date_captures {
-        // Date is in the "YYYY-MM-DD" format
-        let year = captures[1].parse::<i32>().unwrap();
-        let month = captures[2].parse::<u32>().unwrap();
-        let day = captures[3].parse::<u32>().unwrap();
-        NaiveDateTime::new(
-            NaiveDate::from_ymd_opt(year, month, day).unwrap(),
-            NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
-        )
-    } else if let Some(captures) = formatter_captures {
-        // Date is in the "MM/YYYY" format
-        let month = captures[1].parse::<u32>().unwrap();
-        let year = captures[2].parse::<i32>().unwrap();
-        NaiveDateTime::new(
-            NaiveDate::from_ymd_opt(year, month, 1).unwrap(),
-            NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
-        )
-    } else {
-        // Unrecognized date format
-        return Local::now();
-    };
-
-    // Convert the NaiveDateTime object to a DateTime object in the local timezone
-    expanded_date.and_local_timezone(Local).unwrap()
-}
-
-#[derive(Deserialize, Serialize, Default, Clone, Debug, Builder, PartialEq)]
-#[serde(rename_all(deserialize = "camelCase", serialize = "camelCase"))]
-#[builder(setter(into), default)]
-pub struct Creator {
-    #[serde(alias = "creatorType")]
-    pub creator_type: String,
-    #[serde(alias = "firstName")]
-    pub first_name: String,
-    #[serde(alias = "lastName")]
-    pub last_name: String,
-}
+    /// Whether this item is in the trash.
+    pub fn is_deleted(&self) -> bool {
+        self.data.is_deleted()
+    }
 
-impl Creator {
-    pub fn full_name(&self) -> String {
-        format!("{} {}", self.first_name, self.last_name)
+    /// Full names of the creators, separated by commas. Empty for items without creators.
+    pub fn author(&self) -> String {
+        self.data
+            .creators()
+            .iter()
+            .map(|c| c.full_name())
+            .collect::<Vec<String>>()
+            .join(", ")
     }
 
-    pub fn short_name(&self) -> String {
-        match self.first_name.chars().next() {
-            Some(first_initial) => format!("{}. {}", first_initial, self.last_name),
-            None => self.last_name.to_string(),
+    /// The date of the item. The `parsedDate` computed by the Zotero API is trusted for the year, month and day;
+    /// the date field itself tells whether the date is a season or an approximation.
+    /// Returns `None` if the item has no date or if it can not be understood.
+    pub fn date(&self) -> Option<ZoteroDate> {
+        let date_str = self.data.date().unwrap_or("");
+        let hint = self
+            .meta
+            .parsed_date
+            .as_deref()
+            .and_then(ZoteroDate::from_parsed_date);
+        match (hint, date_str.parse::<ZoteroDate>()) {
+            (Some(hint), Ok(parsed)) => Some(ZoteroDate {
+                season: parsed.season,
+                circa: parsed.circa,
+                ..hint
+            }),
+            (Some(hint), Err(_)) => Some(hint),
+            (None, parsed) => parsed.ok(),
         }
     }
 }
@@ -574,7 +269,27 @@ impl Default for SizeOrBool {
     }
 }
 
+/// An item of a user library holding `data`, for the tests of the item modules.
 #[cfg(test)]
+pub(crate) fn test_item(data: ItemType) -> Item {
+    serde_json::from_value(serde_json::json!({
+        "key": data.key(),
+        "version": 1,
+        "library": {
+            "type": "user",
+            "id": 1,
+            "name": "john.doe",
+            "links": {"alternate": {"href": "https://www.zotero.org/john.doe", "type": "text/html"}}
+        },
+        "links": {"alternate": {"href": "https://www.zotero.org/john.doe/items", "type": "text/html"}},
+        "meta": {},
+        "data": data
+    }))
+    .unwrap()
+}
+
+#[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod test_item_deserialization {
     use super::*;
     #[test]
@@ -1791,8 +1506,8 @@ mod test_item_deserialization {
 
     #[test]
     fn test_deserialize_creators() {
-        let expected_output = Creator {
-            creator_type: "author".into(),
+        let expected_output = Creator::TwoField {
+            creator_type: CreatorType::Author,
             first_name: "John".into(),
             last_name: "Doe".into(),
         };
@@ -1809,6 +1524,180 @@ mod test_item_deserialization {
         assert_eq!(result, expected_output);
     }
 
+    #[test]
+    fn test_creator_types_validation() {
+        let input = r#"
+            {
+                "key": "K36LXQI5",
+                "version": 2437,
+                "itemType": "journalArticle",
+                "title": "World health statistics",
+                "creators": [
+                    {"creatorType": "author", "name": "WHO"},
+                    {"creatorType": "inventor", "firstName": "John", "lastName": "Doe"}
+                ],
+                "tags": [],
+                "relations": {},
+                "dateAdded": "2019-10-01T13:34:30Z",
+                "dateModified": "2019-10-01T13:34:30Z"
+            }
+        "#;
+
+        let result = serde_json::from_str::<ItemType>(input).unwrap();
+        assert_eq!(result.kind(), Some(ItemTypeKind::JournalArticle));
+        assert_eq!(
+            result.creators()[0],
+            Creator::single_field(CreatorType::Author, "WHO")
+        );
+        assert_eq!(
+            result.invalid_creators(),
+            vec![&Creator::two_field(CreatorType::Inventor, "John", "Doe")]
+        );
+    }
+
+    #[test]
+    fn test_generated_item_serialization() {
+        let case = CaseDataBuilder::default()
+            .case_name("Marbury v. Madison")
+            .short_title("Marbury")
+            .build()
+            .unwrap();
+
+        assert_eq!(case.title(), "Marbury v. Madison");
+        assert_eq!(
+            serde_json::to_value(&case).unwrap(),
+            serde_json::json!({
+                "itemType": "case",
+                "caseName": "Marbury v. Madison",
+                "shortTitle": "Marbury",
+                "version": 0,
+                "tags": [],
+                "collections": null,
+                "relations": {}
+            })
+        );
+
+        let article: JournalArticleData =
+            serde_json::from_str(r#"{"ISSN": "0378-5955", "DOI": "10.1000/182"}"#).unwrap();
+        assert_eq!(article.item_type, "journalArticle");
+        assert_eq!(article.issn, "0378-5955");
+        assert_eq!(article.doi, "10.1000/182");
+    }
+
+    #[test]
+    fn test_newer_item_types_deserialization() {
+        let input = r#"[
+            {"key": "AAAAAAAA", "version": 1, "itemType": "preprint", "title": "A preprint",
+             "repository": "arXiv", "archiveID": "arXiv:2101.00001", "DOI": "10.48550/arXiv.2101.00001"},
+            {"key": "BBBBBBBB", "version": 1, "itemType": "dataset", "title": "A dataset", "type": "CSV"},
+            {"key": "CCCCCCCC", "version": 1, "itemType": "videoGame", "title": "A game", "platform": "PC"}
+        ]"#;
+
+        let result = serde_json::from_str::<Vec<ItemType>>(input).unwrap();
+        assert_eq!(result.len(), 3);
+
+        match &result[0] {
+            ItemType::Preprint(d) => {
+                assert_eq!(d.repository, "arXiv");
+                assert_eq!(d.archive_id, "arXiv:2101.00001");
+            }
+            other => panic!("expected a preprint, got {:?}", other),
+        }
+        match &result[1] {
+            ItemType::Dataset(d) => assert_eq!(d.r#type, "CSV"),
+            other => panic!("expected a dataset, got {:?}", other),
+        }
+
+        let unknown = &result[2];
+        assert!(matches!(unknown, ItemType::Unknown(_)));
+        assert_eq!(unknown.kind(), None);
+        assert_eq!(unknown.title(), "A game");
+        assert_eq!(unknown.key(), "CCCCCCCC");
+        assert_eq!(
+            serde_json::to_value(unknown).unwrap()["platform"],
+            serde_json::json!("PC")
+        );
+        assert_eq!(
+            unknown.validate()[0].kind,
+            DiagnosticKind::UnknownItemType("videoGame".to_string())
+        );
+
+        let serialized = serde_json::to_value(&result[0]).unwrap();
+        assert_eq!(serialized["itemType"], "preprint");
+        assert_eq!(serialized["archiveID"], "arXiv:2101.00001");
+
+        assert!(serde_json::from_str::<ItemType>(r#"{"title": "no type"}"#).is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields_round_trip() {
+        let input = serde_json::json!({
+            "key": "K36LXQI5",
+            "version": 2437,
+            "itemType": "journalArticle",
+            "title": "Lorem",
+            "ISSN": "0378-5955",
+            "futureField": "kept",
+            "futureObject": {"nested": [1, 2]},
+            "tags": [],
+            "collections": [],
+            "relations": {}
+        });
+
+        let item = serde_json::from_value::<ItemType>(input.clone()).unwrap();
+        match &item {
+            ItemType::JournalArticle(d) => {
+                assert_eq!(d.issn, "0378-5955");
+                assert_eq!(d.unknown_fields.len(), 2);
+                assert_eq!(d.unknown_fields["futureField"], "kept");
+            }
+            other => panic!("expected a journal article, got {:?}", other),
+        }
+
+        let output = serde_json::to_value(&item).unwrap();
+        assert_eq!(output["futureField"], input["futureField"]);
+        assert_eq!(output["futureObject"], input["futureObject"]);
+        assert_eq!(output["ISSN"], input["ISSN"]);
+
+        let note = serde_json::from_str::<NoteData>(
+            r#"{"key": "AAAAAAAA", "version": 1, "note": "<p>Lorem</p>", "tags": [], "relations": {}, "dateAdded": "", "dateModified": "", "futureField": true}"#,
+        )
+        .unwrap();
+        assert_eq!(serde_json::to_value(&note).unwrap()["futureField"], true);
+    }
+
+    #[test]
+    fn test_deleted_flag() {
+        let trashed = serde_json::from_str::<ItemType>(
+            r#"{"key": "AAAAAAAA", "itemType": "book", "title": "Lorem", "deleted": 1}"#,
+        )
+        .unwrap();
+        assert!(trashed.is_deleted());
+        assert_eq!(serde_json::to_value(&trashed).unwrap()["deleted"], 1);
+
+        let trashed = serde_json::from_str::<ItemType>(
+            r#"{"key": "AAAAAAAA", "version": 1, "itemType": "note", "note": "", "tags": [], "relations": {}, "dateAdded": "", "dateModified": "", "deleted": true}"#,
+        )
+        .unwrap();
+        assert!(trashed.is_deleted());
+
+        let restored = serde_json::from_str::<ItemType>(
+            r#"{"key": "AAAAAAAA", "itemType": "book", "title": "Lorem", "deleted": 0}"#,
+        )
+        .unwrap();
+        assert!(!restored.is_deleted());
+        assert!(serde_json::to_value(&restored)
+            .unwrap()
+            .get("deleted")
+            .is_none());
+
+        let unknown = serde_json::from_str::<ItemType>(
+            r#"{"key": "AAAAAAAA", "itemType": "futureType", "deleted": 1}"#,
+        )
+        .unwrap();
+        assert!(unknown.is_deleted());
+    }
+
     #[test]
     fn test_item_meta_deserialization() {
         let expected_output = ItemMeta {
@@ -1899,4 +1788,183 @@ mod test_item_deserialization {
         serde_json::from_str::<Item>(input).expect("Failed to deserialize item");
         assert!(true);
     }
+
+    #[test]
+    fn test_item_unknown_fields() {
+        let input = serde_json::json!({
+            "key": "K36LXQI5",
+            "version": 2437,
+            "library": {
+                "type": "user",
+                "id": 1000000,
+                "name": "john.doe",
+                "links": {"alternate": {"href": "https://www.zotero.org/john.doe", "type": "text/html"}}
+            },
+            "links": {"alternate": {"href": "https://www.zotero.org/john.doe/items/K36LXQI5", "type": "text/html"}},
+            "meta": {"numChildren": 0},
+            "bib": "<div class=\"csl-bib-body\"></div>",
+            "data": {"key": "K36LXQI5", "version": 2437, "itemType": "book", "title": "Lorem"}
+        });
+
+        let item = serde_json::from_value::<Item>(input).unwrap();
+        assert_eq!(
+            item.unknown_fields["bib"],
+            "<div class=\"csl-bib-body\"></div>"
+        );
+        assert!(serde_json::to_value(&item).unwrap().get("bib").is_some());
+    }
+
+    #[test]
+    fn test_item_date() {
+        let item = |date: &str, parsed_date: Option<&str>| {
+            serde_json::from_value::<Item>(serde_json::json!({
+                "key": "K36LXQI5",
+                "version": 1,
+                "library": {
+                    "type": "user",
+                    "id": 1000000,
+                    "name": "john.doe",
+                    "links": {"alternate": {"href": "https://www.zotero.org/john.doe", "type": "text/html"}}
+                },
+                "links": {"alternate": {"href": "https://www.zotero.org/john.doe/items/K36LXQI5", "type": "text/html"}},
+                "meta": {"parsedDate": parsed_date},
+                "data": {"key": "K36LXQI5", "version": 1, "itemType": "book", "title": "Lorem", "date": date}
+            }))
+            .unwrap()
+        };
+
+        let date = item("circa 1850", Some("1850")).date().unwrap();
+        assert_eq!(date.precision(), DatePrecision::Year);
+        assert!(date.circa);
+
+        // parsedDate wins over the local parser, e.g. for day-first dates
+        let date = item("04/05/2021", Some("2021-05-04")).date().unwrap();
+        assert_eq!(date.sort_key(), "2021-05-04");
+
+        let date = item("Winter 2001", None).date().unwrap();
+        assert_eq!(date.season, Some(Season::Winter));
+
+        assert_eq!(item("", None).date(), None);
+        assert_eq!(item("2020-02-31", None).date(), None);
+    }
+
+    #[test]
+    fn test_item_fields() {
+        let mut case = ItemType::Case(
+            CaseDataBuilder::default()
+                .case_name("Marbury v. Madison")
+                .date_decided("1803")
+                .creators(vec![Creator::two_field("author", "John", "Marshall")])
+                .build()
+                .unwrap(),
+        );
+        assert_eq!(case.date(), Some("1803"));
+        assert_eq!(case.creators().len(), 1);
+        assert_eq!(case.doi(), None);
+        assert_eq!(case.parent_item(), None);
+
+        *case.date_mut().unwrap() = "1803-02-24".to_string();
+        case.collections_mut().unwrap().push("TYPDZEZF".to_string());
+        match &case {
+            ItemType::Case(d) => {
+                assert_eq!(d.date_decided, "1803-02-24");
+                assert_eq!(d.collections, Some(vec!["TYPDZEZF".to_string()]));
+            }
+            other => panic!("expected a case, got {:?}", other),
+        }
+
+        let podcast = ItemType::Podcast(PodcastDataBuilder::default().build().unwrap());
+        assert_eq!(podcast.date(), None);
+        assert_eq!(podcast.url(), Some(""));
+
+        let mut note = ItemType::Note(
+            NoteDataBuilder::default()
+                .parent_item("ABCD2345")
+                .build()
+                .unwrap(),
+        );
+        assert_eq!(note.parent_item(), Some("ABCD2345"));
+        assert!(note.creators_mut().is_none());
+
+        let mut unknown: ItemType = serde_json::from_value(serde_json::json!({
+            "itemType": "videoGame",
+            "date": "2001",
+            "extra": "",
+            "creators": [{"creatorType": "programmer", "name": "Studio"}],
+            "tags": [{"tag": "retro"}],
+            "collections": ["C1", "C2"]
+        }))
+        .unwrap();
+        assert_eq!(unknown.date(), Some("2001"));
+        unknown.extra_mut().unwrap().push_str("Platform: PC");
+        assert_eq!(unknown.extra(), Some("Platform: PC"));
+        assert_eq!(unknown.url(), None);
+        assert!(unknown.has_tag("retro".to_string()));
+        assert_eq!(
+            unknown.creators()[0].creator_type(),
+            &CreatorType::Programmer
+        );
+        assert_eq!(unknown.collections(), ["C1", "C2"]);
+        unknown.collections_mut().unwrap().push("C3".to_string());
+        assert_eq!(
+            serde_json::to_value(&unknown).unwrap()["collections"],
+            serde_json::json!(["C1", "C2", "C3"])
+        );
+    }
+
+    #[test]
+    fn test_field_access_by_name() {
+        let mut article =
+            ItemType::JournalArticle(JournalArticleDataBuilder::default().build().unwrap());
+        article
+            .set_field("publicationTitle", "Hearing Research")
+            .unwrap();
+        article.set_field("ISSN", "0378-5955").unwrap();
+        article.set_field("title", "Lorem").unwrap();
+        assert_eq!(
+            article.get_field("publicationTitle"),
+            Some("Hearing Research")
+        );
+        assert_eq!(article.get_field("ISSN"), Some("0378-5955"));
+        assert_eq!(article.title(), "Lorem");
+        assert_eq!(article.get_field("key"), None);
+        assert_eq!(
+            article.set_field("caseName", "Lorem"),
+            Err(FieldError::UnknownField("caseName".to_string()))
+        );
+
+        let fields: Vec<(&str, &str)> = article.fields().filter(|(_, v)| !v.is_empty()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("title", "Lorem"),
+                ("publicationTitle", "Hearing Research"),
+                ("ISSN", "0378-5955")
+            ]
+        );
+
+        // base fields are accepted as aliases of type-specific fields
+        let mut case = ItemType::Case(CaseDataBuilder::default().build().unwrap());
+        case.set_field("title", "Marbury v. Madison").unwrap();
+        case.set_field("date", "1803").unwrap();
+        assert_eq!(case.get_field("caseName"), Some("Marbury v. Madison"));
+        assert_eq!(case.get_field("dateDecided"), Some("1803"));
+        assert!(case.fields().any(|(name, _)| name == "caseName"));
+        assert!(!case.fields().any(|(name, _)| name == "title"));
+
+        let dataset =
+            ItemType::Dataset(DatasetDataBuilder::default().r#type("CSV").build().unwrap());
+        assert_eq!(dataset.get_field("type"), Some("CSV"));
+
+        let note = ItemType::Note(
+            NoteDataBuilder::default()
+                .note("<p>Lorem</p>")
+                .build()
+                .unwrap(),
+        );
+        assert_eq!(
+            note.fields().collect::<Vec<_>>(),
+            vec![("parentItem", ""), ("note", "<p>Lorem</p>")]
+        );
+    }
 }
@@ -0,0 +1,397 @@
+//! Items arranged by parent: top-level items with their notes and attachments, and attachments with their
+//! annotations.
+//!
+//! ```rust
+//! use zotero_data::item::{Item, ItemTree};
+//!
+//! # let items: Vec<Item> = Vec::new();
+//! let tree = ItemTree::from_items(items);
+//! for (depth, item) in tree.iter() {
+//!     println!("{}{}", "  ".repeat(depth), item.title());
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::item::{Item, ItemType};
+use crate::shared_fields::ItemFields;
+
+/// Error returned by [`ItemTree::reparent`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemTreeError {
+    /// No item with this key in the tree.
+    UnknownItem(String),
+    /// The item is a regular item, which can't have a parent.
+    NotAChild(String),
+    /// The new parent is the item itself or one of its descendants.
+    Cycle(String),
+    /// The item type of the new parent can't have this child: annotations belong to attachments, notes and
+    /// attachments to regular items.
+    InvalidParent { child: String, parent: String },
+}
+
+impl fmt::Display for ItemTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemTreeError::UnknownItem(key) => write!(f, "no item {} in the tree", key),
+            ItemTreeError::NotAChild(key) => write!(f, "item {} can't have a parent", key),
+            ItemTreeError::Cycle(key) => {
+                write!(
+                    f,
+                    "item {} can't be moved under one of its descendants",
+                    key
+                )
+            }
+            ItemTreeError::InvalidParent { child, parent } => {
+                write!(f, "item {} can't be a child of item {}", child, parent)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ItemTreeError {}
+
+/// An item with its children.
+#[derive(Clone, Debug)]
+pub struct ItemNode {
+    pub item: Item,
+    pub children: Vec<ItemNode>,
+}
+
+impl ItemNode {
+    pub fn key(&self) -> &str {
+        &self.item.key
+    }
+
+    /// Child notes.
+    pub fn notes(&self) -> impl Iterator<Item = &Item> {
+        self.children_matching(|data| matches!(data, ItemType::Note(_)))
+    }
+
+    /// Child attachments.
+    pub fn attachments(&self) -> impl Iterator<Item = &Item> {
+        self.children_matching(|data| matches!(data, ItemType::Attachment(_)))
+    }
+
+    /// Child annotations, i.e. the annotations of an attachment.
+    pub fn annotations(&self) -> impl Iterator<Item = &Item> {
+        self.children_matching(|data| matches!(data, ItemType::Annotation(_)))
+    }
+
+    /// The item and its descendants, depth-first, with their depth relative to this item.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Item)> {
+        let mut stack = vec![(0, self)];
+        std::iter::from_fn(move || {
+            let (depth, node) = stack.pop()?;
+            stack.extend(node.children.iter().rev().map(|child| (depth + 1, child)));
+            Some((depth, &node.item))
+        })
+    }
+
+    fn children_matching<F: Fn(&ItemType) -> bool>(
+        &self,
+        matches: F,
+    ) -> impl Iterator<Item = &Item> {
+        self.children
+            .iter()
+            .map(|child| &child.item)
+            .filter(move |item| matches(&item.data))
+    }
+
+    fn find(&self, key: &str) -> Option<&ItemNode> {
+        match self.key() == key {
+            true => Some(self),
+            false => self.children.iter().find_map(|child| child.find(key)),
+        }
+    }
+
+    fn find_mut(&mut self, key: &str) -> Option<&mut ItemNode> {
+        match self.key() == key {
+            true => Some(self),
+            false => self
+                .children
+                .iter_mut()
+                .find_map(|child| child.find_mut(key)),
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> Option<ItemNode> {
+        match self.children.iter().position(|child| child.key() == key) {
+            Some(i) => Some(self.children.remove(i)),
+            None => self.children.iter_mut().find_map(|child| child.remove(key)),
+        }
+    }
+
+    fn into_items(self, items: &mut Vec<Item>) {
+        items.push(self.item);
+        for child in self.children {
+            child.into_items(items);
+        }
+    }
+}
+
+/// Items arranged by parent.
+#[derive(Clone, Debug, Default)]
+pub struct ItemTree {
+    pub roots: Vec<ItemNode>,
+}
+
+impl ItemTree {
+    /// Arrange downloaded items by their `parentItem`. Items keep their order among their siblings.
+    /// Children whose parent is not in `items` are kept as roots.
+    pub fn from_items(items: Vec<Item>) -> ItemTree {
+        let keys: HashSet<&str> = items.iter().map(|item| item.key.as_str()).collect();
+        let has_parent: Vec<bool> = items
+            .iter()
+            .map(|item| matches!(item.data.parent_item(), Some(parent) if keys.contains(parent)))
+            .collect();
+        let mut children: HashMap<String, Vec<Item>> = HashMap::new();
+        let mut roots = Vec::new();
+        for (item, has_parent) in items.into_iter().zip(has_parent) {
+            match (has_parent, item.data.parent_item()) {
+                (true, Some(parent)) => children.entry(parent.to_string()).or_default().push(item),
+                _ => roots.push(item),
+            }
+        }
+
+        fn node(item: Item, children: &mut HashMap<String, Vec<Item>>) -> ItemNode {
+            let item_children = children.remove(&item.key).unwrap_or_default();
+            ItemNode {
+                children: item_children
+                    .into_iter()
+                    .map(|child| node(child, children))
+                    .collect(),
+                item,
+            }
+        }
+        let mut roots: Vec<ItemNode> = roots
+            .into_iter()
+            .map(|item| node(item, &mut children))
+            .collect();
+        // items that are their own ancestors are not reachable from a root
+        for (_, items) in children.drain() {
+            roots.extend(items.into_iter().map(|item| ItemNode {
+                item,
+                children: Vec::new(),
+            }));
+        }
+        ItemTree { roots }
+    }
+
+    /// Every item, depth-first, with its depth: 0 for roots, 1 for their children and so on.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Item)> {
+        self.roots.iter().flat_map(ItemNode::iter)
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ItemNode> {
+        self.roots.iter().find_map(|root| root.find(key))
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut ItemNode> {
+        self.roots.iter_mut().find_map(|root| root.find_mut(key))
+    }
+
+    /// The parent of an item, `None` for roots and unknown items.
+    pub fn parent(&self, key: &str) -> Option<&Item> {
+        fn search<'a>(node: &'a ItemNode, key: &str) -> Option<&'a Item> {
+            match node.children.iter().any(|child| child.key() == key) {
+                true => Some(&node.item),
+                false => node.children.iter().find_map(|child| search(child, key)),
+            }
+        }
+        self.roots.iter().find_map(|root| search(root, key))
+    }
+
+    /// Move a note, an attachment or an annotation under `new_parent`, updating its `parentItem`. Notes and
+    /// attachments can only be moved under regular items, and annotations under attachments.
+    /// Send the returned item to Zotero, e.g. with a PATCH of its `parentItem`, to apply the change.
+    pub fn reparent(&mut self, key: &str, new_parent: &str) -> Result<&Item, ItemTreeError> {
+        let node = self
+            .get(key)
+            .ok_or_else(|| ItemTreeError::UnknownItem(key.to_string()))?;
+        if node.item.data.parent_item().is_none() {
+            return Err(ItemTreeError::NotAChild(key.to_string()));
+        }
+        if node.find(new_parent).is_some() {
+            return Err(ItemTreeError::Cycle(key.to_string()));
+        }
+        let parent = self
+            .get(new_parent)
+            .ok_or_else(|| ItemTreeError::UnknownItem(new_parent.to_string()))?;
+        let valid_parent = match (&node.item.data, &parent.item.data) {
+            (ItemType::Annotation(_), parent) => matches!(parent, ItemType::Attachment(_)),
+            (_, ItemType::Annotation(_) | ItemType::Attachment(_) | ItemType::Note(_)) => false,
+            _ => true,
+        };
+        if !valid_parent {
+            return Err(ItemTreeError::InvalidParent {
+                child: key.to_string(),
+                parent: new_parent.to_string(),
+            });
+        }
+
+        let mut node = match self.roots.iter().position(|root| root.key() == key) {
+            Some(i) => self.roots.remove(i),
+            None => self
+                .roots
+                .iter_mut()
+                .find_map(|root| root.remove(key))
+                .ok_or_else(|| ItemTreeError::UnknownItem(key.to_string()))?,
+        };
+        if let Some(parent_item) = node.item.data.parent_item_mut() {
+            *parent_item = new_parent.to_string();
+        }
+        let parent = self
+            .get_mut(new_parent)
+            .ok_or_else(|| ItemTreeError::UnknownItem(new_parent.to_string()))?;
+        parent.children.push(node);
+        Ok(&parent.children[parent.children.len() - 1].item)
+    }
+
+    /// All the items, parents before their children.
+    pub fn into_items(self) -> Vec<Item> {
+        let mut items = Vec::new();
+        for root in self.roots {
+            root.into_items(&mut items);
+        }
+        items
+    }
+}
+
+#[cfg(test)]
+mod test_tree {
+    use super::*;
+    use crate::item::{
+        test_item, AnnotationDataBuilder, AnnotationType, AttachmentDataBuilder, BookDataBuilder,
+        LinkMode, NoteDataBuilder,
+    };
+
+    fn items() -> Vec<Item> {
+        vec![
+            test_item(ItemType::Annotation(
+                AnnotationDataBuilder::default()
+                    .key("ANNOTAT1")
+                    .parent_item("ATTACHM1")
+                    .annotation_type(AnnotationType::Highlight)
+                    .build()
+                    .unwrap(),
+            )),
+            test_item(ItemType::Book(
+                BookDataBuilder::default()
+                    .key("BOOK0001")
+                    .title("Lorem")
+                    .build()
+                    .unwrap(),
+            )),
+            test_item(ItemType::Note(
+                NoteDataBuilder::default()
+                    .key("NOTE0001")
+                    .parent_item("BOOK0001")
+                    .note("<p>Ipsum</p>")
+                    .build()
+                    .unwrap(),
+            )),
+            test_item(ItemType::Attachment(
+                AttachmentDataBuilder::default()
+                    .key("ATTACHM1")
+                    .parent_item("BOOK0001")
+                    .link_mode(LinkMode::ImportedFile)
+                    .build()
+                    .unwrap(),
+            )),
+            test_item(ItemType::Book(
+                BookDataBuilder::default()
+                    .key("BOOK0002")
+                    .title("Dolor")
+                    .build()
+                    .unwrap(),
+            )),
+            test_item(ItemType::Note(
+                NoteDataBuilder::default()
+                    .key("ORPHAN01")
+                    .parent_item("MISSING1")
+                    .note("<p>Sit</p>")
+                    .build()
+                    .unwrap(),
+            )),
+        ]
+    }
+
+    #[test]
+    fn assemble() {
+        let tree = ItemTree::from_items(items());
+        assert_eq!(
+            tree.iter()
+                .map(|(depth, item)| (depth, item.key.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "BOOK0001"),
+                (1, "NOTE0001"),
+                (1, "ATTACHM1"),
+                (2, "ANNOTAT1"),
+                (0, "BOOK0002"),
+                (0, "ORPHAN01"),
+            ]
+        );
+        assert_eq!(tree.len(), 6);
+
+        let book = tree.get("BOOK0001").unwrap();
+        assert_eq!(book.notes().count(), 1);
+        assert_eq!(book.attachments().next().unwrap().key, "ATTACHM1");
+        assert_eq!(tree.get("ATTACHM1").unwrap().annotations().count(), 1);
+        assert_eq!(tree.parent("ANNOTAT1").unwrap().key, "ATTACHM1");
+        assert!(tree.parent("BOOK0001").is_none());
+    }
+
+    #[test]
+    fn reparent() {
+        let mut tree = ItemTree::from_items(items());
+
+        let moved = tree.reparent("NOTE0001", "BOOK0002").unwrap();
+        assert_eq!(moved.data.parent_item(), Some("BOOK0002"));
+        assert_eq!(tree.get("BOOK0001").unwrap().notes().count(), 0);
+        assert_eq!(tree.parent("NOTE0001").unwrap().key, "BOOK0002");
+
+        tree.reparent("ORPHAN01", "BOOK0001").unwrap();
+        assert_eq!(tree.roots.len(), 2);
+
+        assert_eq!(
+            tree.reparent("BOOK0001", "BOOK0002").unwrap_err(),
+            ItemTreeError::NotAChild("BOOK0001".to_string())
+        );
+        assert_eq!(
+            tree.reparent("ATTACHM1", "ANNOTAT1").unwrap_err(),
+            ItemTreeError::Cycle("ATTACHM1".to_string())
+        );
+        assert_eq!(
+            tree.reparent("NOTE0001", "UNKNOWN1").unwrap_err(),
+            ItemTreeError::UnknownItem("UNKNOWN1".to_string())
+        );
+        for (child, parent) in [
+            ("NOTE0001", "ORPHAN01"),
+            ("NOTE0001", "ATTACHM1"),
+            ("ANNOTAT1", "BOOK0002"),
+        ] {
+            assert_eq!(
+                tree.reparent(child, parent).unwrap_err(),
+                ItemTreeError::InvalidParent {
+                    child: child.to_string(),
+                    parent: parent.to_string()
+                }
+            );
+        }
+
+        let items = tree.into_items();
+        assert_eq!(items.len(), 6);
+        assert_eq!(items[0].key, "BOOK0001");
+    }
+}
@@ -0,0 +1,446 @@
+//! Schema-driven validation of Zotero items.
+//!
+//! ```rust
+//! use zotero_data::item::{BookDataBuilder, ItemType};
+//!
+//! let book = ItemType::Book(
+//!     BookDataBuilder::default()
+//!         .title("Book title")
+//!         .isbn("978-3-16-148410-1")
+//!         .build()
+//!         .unwrap(),
+//! );
+//!
+//! for diagnostic in book.validate() {
+//!     println!("{}", diagnostic);
+//! }
+//! ```
+
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+
+use crate::item::{CreatorType, ItemType, ItemTypeKind, LinkMode, ZoteroDate};
+
+static ISO_DATE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\d{4})-(\d{2})-(\d{2})").unwrap());
+
+static DOI_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^10\.\d{4,9}/\S+$").unwrap());
+
+static CONTENT_TYPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?i)(application|audio|font|image|message|model|multipart|text|video)/[a-z0-9!#$&^_.+-]+$")
+        .unwrap()
+});
+
+/// How serious a [`Diagnostic`] is. Items with errors will be rejected by the Zotero API or hold malformed data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// What went wrong with a field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiagnosticKind {
+    MissingField,
+    InvalidCreatorType(CreatorType),
+    InvalidLinkMode(String),
+    InvalidContentType(String),
+    InvalidDate(String),
+    InvalidIsbn(String),
+    InvalidIssn(String),
+    InvalidDoi(String),
+    /// The item type is not part of the schema known to this crate, so its fields were not checked.
+    UnknownItemType(String),
+}
+
+/// A single problem found by [`ItemType::validate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Zotero name of the offending field, e.g. `ISBN` or `creators`.
+    pub field: String,
+    pub kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    fn error<S: Into<String>>(field: S, kind: DiagnosticKind) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            field: field.into(),
+            kind,
+        }
+    }
+
+    fn warning<S: Into<String>>(field: S, kind: DiagnosticKind) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            field: field.into(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{} in `{}`: ", severity, self.field)?;
+        match &self.kind {
+            DiagnosticKind::MissingField => write!(f, "field is missing"),
+            DiagnosticKind::InvalidCreatorType(t) => {
+                write!(f, "creator type \"{}\" is not valid for this item type", t)
+            }
+            DiagnosticKind::InvalidLinkMode(v) => write!(f, "\"{}\" is not a valid link mode", v),
+            DiagnosticKind::InvalidContentType(v) => {
+                write!(f, "\"{}\" is not a valid content type", v)
+            }
+            DiagnosticKind::InvalidDate(v) => write!(f, "\"{}\" is not a valid date", v),
+            DiagnosticKind::InvalidIsbn(v) => write!(f, "\"{}\" is not a valid ISBN", v),
+            DiagnosticKind::InvalidIssn(v) => write!(f, "\"{}\" is not a valid ISSN", v),
+            DiagnosticKind::InvalidDoi(v) => write!(f, "\"{}\" is not a valid DOI", v),
+            DiagnosticKind::UnknownItemType(v) => write!(f, "\"{}\" is not a known item type", v),
+        }
+    }
+}
+
+impl ItemType {
+    /// Check the item against the Zotero schema: required fields, creator types, attachment link modes
+    /// and content types, dates, ISBN and ISSN checksums and DOI syntax.
+    /// An empty list means the item is valid.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let kind = match (self.kind(), self) {
+            (Some(kind), _) => kind,
+            (None, ItemType::Unknown(data)) => {
+                let item_type = data.item_type();
+                diagnostics.push(Diagnostic::warning(
+                    "itemType",
+                    DiagnosticKind::UnknownItemType(item_type.to_string()),
+                ));
+                return diagnostics;
+            }
+            (None, _) => return diagnostics,
+        };
+        let fields = match serde_json::to_value(self) {
+            Ok(Value::Object(fields)) => fields,
+            _ => return diagnostics,
+        };
+        let field = |name: &str| fields.get(name).and_then(Value::as_str).unwrap_or("");
+
+        if let Some(title_field) = kind.title_field() {
+            if field(title_field).trim().is_empty() {
+                diagnostics.push(Diagnostic::warning(
+                    title_field,
+                    DiagnosticKind::MissingField,
+                ));
+            }
+        }
+
+        for creator in self.invalid_creators() {
+            diagnostics.push(Diagnostic::error(
+                "creators",
+                DiagnosticKind::InvalidCreatorType(creator.creator_type().clone()),
+            ));
+        }
+
+        if kind == ItemTypeKind::Attachment {
+            validate_attachment(&field, &mut diagnostics);
+        }
+
+        for name in kind.fields().iter().filter(|f| kind.is_date_field(f)) {
+            let value = field(name);
+            if !value.is_empty() && !is_valid_date(value) {
+                let invalid_date = DiagnosticKind::InvalidDate(value.to_string());
+                diagnostics.push(match ISO_DATE_REGEX.is_match(value) {
+                    true => Diagnostic::error(*name, invalid_date),
+                    false => Diagnostic::warning(*name, invalid_date),
+                });
+            }
+        }
+
+        let access_date = field("accessDate");
+        if !access_date.is_empty() && !is_valid_access_date(access_date) {
+            diagnostics.push(Diagnostic::error(
+                "accessDate",
+                DiagnosticKind::InvalidDate(access_date.to_string()),
+            ));
+        }
+
+        for isbn in split_identifiers(field("ISBN")) {
+            if !is_valid_isbn(isbn) {
+                diagnostics.push(Diagnostic::error(
+                    "ISBN",
+                    DiagnosticKind::InvalidIsbn(isbn.to_string()),
+                ));
+            }
+        }
+
+        for issn in split_identifiers(field("ISSN")) {
+            if !is_valid_issn(issn) {
+                diagnostics.push(Diagnostic::error(
+                    "ISSN",
+                    DiagnosticKind::InvalidIssn(issn.to_string()),
+                ));
+            }
+        }
+
+        let doi = field("DOI");
+        if !doi.is_empty() && !is_valid_doi(doi) {
+            diagnostics.push(Diagnostic::error(
+                "DOI",
+                DiagnosticKind::InvalidDoi(doi.to_string()),
+            ));
+        }
+
+        diagnostics
+    }
+
+    /// Returns `true` if [`ItemType::validate`] reports no error. Warnings are ignored.
+    pub fn is_valid(&self) -> bool {
+        self.validate()
+            .iter()
+            .all(|d| d.severity != Severity::Error)
+    }
+}
+
+fn validate_attachment<'a, F: Fn(&str) -> &'a str>(field: &F, diagnostics: &mut Vec<Diagnostic>) {
+    let link_mode = field("linkMode");
+    if link_mode.is_empty() {
+        diagnostics.push(Diagnostic::error("linkMode", DiagnosticKind::MissingField));
+    } else if matches!(LinkMode::from(link_mode), LinkMode::Other(_)) {
+        diagnostics.push(Diagnostic::error(
+            "linkMode",
+            DiagnosticKind::InvalidLinkMode(link_mode.to_string()),
+        ));
+    }
+
+    let content_type = field("contentType");
+    if !content_type.is_empty() && !CONTENT_TYPE_REGEX.is_match(content_type) {
+        diagnostics.push(Diagnostic::error(
+            "contentType",
+            DiagnosticKind::InvalidContentType(content_type.to_string()),
+        ));
+    }
+
+    match LinkMode::from(link_mode) {
+        LinkMode::ImportedUrl | LinkMode::LinkedUrl if field("url").is_empty() => {
+            diagnostics.push(Diagnostic::error("url", DiagnosticKind::MissingField));
+        }
+        LinkMode::EmbeddedImage if field("parentItem").is_empty() => {
+            diagnostics.push(Diagnostic::error(
+                "parentItem",
+                DiagnosticKind::MissingField,
+            ));
+        }
+        LinkMode::EmbeddedImage if !content_type.to_lowercase().starts_with("image/") => {
+            diagnostics.push(Diagnostic::error(
+                "contentType",
+                DiagnosticKind::InvalidContentType(content_type.to_string()),
+            ));
+        }
+        _ => {}
+    }
+}
+
+/// Zotero stores several identifiers in the same field, separated by commas, semicolons or new lines. Spaces
+/// are kept: they may separate the groups of digits of a single identifier.
+pub(crate) fn split_identifiers(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split([',', ';', '\n'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Zotero dates are free-form, but they must at least contain a year and must not name a day that does not exist.
+/// See [`ZoteroDate`].
+pub fn is_valid_date(date: &str) -> bool {
+    date.parse::<ZoteroDate>().is_ok()
+}
+
+/// Access dates must be in one of the formats accepted by the Zotero API.
+pub fn is_valid_access_date(date: &str) -> bool {
+    date == "CURRENT_TIMESTAMP"
+        || NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%SZ").is_ok()
+        || NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").is_ok()
+        || NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+}
+
+/// Check an ISBN-10 or ISBN-13 checksum. Spaces and hyphens are ignored.
+pub fn is_valid_isbn(isbn: &str) -> bool {
+    let chars: Vec<char> = isbn.chars().filter(|c| *c != '-' && *c != ' ').collect();
+    match chars.len() {
+        10 => {
+            let mut sum = 0;
+            for (i, c) in chars.iter().enumerate() {
+                let digit = match c {
+                    'X' | 'x' if i == 9 => 10,
+                    c => match c.to_digit(10) {
+                        Some(d) => d,
+                        None => return false,
+                    },
+                };
+                sum += digit * (10 - i as u32);
+            }
+            sum % 11 == 0
+        }
+        13 => {
+            let mut sum = 0;
+            for (i, c) in chars.iter().enumerate() {
+                match c.to_digit(10) {
+                    Some(d) => sum += if i % 2 == 0 { d } else { d * 3 },
+                    None => return false,
+                }
+            }
+            sum % 10 == 0
+        }
+        _ => false,
+    }
+}
+
+/// Check an ISSN checksum, e.g. `0378-5955`. Spaces and hyphens are ignored.
+pub fn is_valid_issn(issn: &str) -> bool {
+    let chars: Vec<char> = issn.chars().filter(|c| *c != '-' && *c != ' ').collect();
+    if chars.len() != 8 {
+        return false;
+    }
+    let mut sum = 0;
+    for (i, c) in chars[..7].iter().enumerate() {
+        match c.to_digit(10) {
+            Some(d) => sum += d * (8 - i as u32),
+            None => return false,
+        }
+    }
+    let check = match chars[7] {
+        'X' | 'x' => 10,
+        c => match c.to_digit(10) {
+            Some(d) => d,
+            None => return false,
+        },
+    };
+    (sum + check) % 11 == 0
+}
+
+/// Check the syntax of a bare DOI, e.g. `10.1000/182`. DOI URLs are not accepted by Zotero's DOI field.
+pub fn is_valid_doi(doi: &str) -> bool {
+    DOI_REGEX.is_match(doi)
+}
+
+#[cfg(test)]
+mod test_validation {
+    use super::*;
+    use crate::item::{AttachmentData, AttachmentDataBuilder, BookDataBuilder, Creator};
+
+    #[test]
+    fn isbn_checksum() {
+        assert!(is_valid_isbn("978-3-16-148410-0"));
+        assert!(is_valid_isbn("0-306-40615-2"));
+        assert!(is_valid_isbn("080442957X"));
+        assert!(is_valid_isbn("978 0 306 40615 7"));
+        assert!(!is_valid_isbn("978-3-16-148410-1"));
+        assert!(!is_valid_isbn("0-306-40615-3"));
+        assert!(!is_valid_isbn("12345"));
+    }
+
+    #[test]
+    fn issn_checksum() {
+        assert!(is_valid_issn("0378-5955"));
+        assert!(is_valid_issn("2434-561X"));
+        assert!(!is_valid_issn("0378-5954"));
+    }
+
+    #[test]
+    fn doi_syntax() {
+        assert!(is_valid_doi("10.1000/182"));
+        assert!(is_valid_doi("10.1038/nphys1170"));
+        assert!(!is_valid_doi("https://doi.org/10.1000/182"));
+        assert!(!is_valid_doi("10.10/182"));
+    }
+
+    #[test]
+    fn dates() {
+        assert!(is_valid_date("2020-02-29"));
+        assert!(is_valid_date("2020-00-00"));
+        assert!(is_valid_date("Spring 1998"));
+        assert!(!is_valid_date("2020-02-31"));
+        assert!(!is_valid_date("unknown"));
+        assert!(is_valid_access_date("2019-10-01T21:17:23Z"));
+        assert!(is_valid_access_date("CURRENT_TIMESTAMP"));
+        assert!(!is_valid_access_date("yesterday"));
+    }
+
+    #[test]
+    fn validate_book() {
+        let book = ItemType::Book(
+            BookDataBuilder::default()
+                .creators(vec![Creator::two_field("inventor", "John", "Doe")])
+                .isbn("978-3-16-148410-0, 978 0 306 40615 7; 0-306-40615-3")
+                .date("2020-02-31")
+                .build()
+                .unwrap(),
+        );
+
+        let diagnostics = book.validate();
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::warning("title", DiagnosticKind::MissingField),
+                Diagnostic::error(
+                    "creators",
+                    DiagnosticKind::InvalidCreatorType(CreatorType::Inventor)
+                ),
+                Diagnostic::error("date", DiagnosticKind::InvalidDate("2020-02-31".into())),
+                Diagnostic::error("ISBN", DiagnosticKind::InvalidIsbn("0-306-40615-3".into())),
+            ]
+        );
+        assert!(!book.is_valid());
+    }
+
+    #[test]
+    fn validate_attachment() {
+        let attachment = ItemType::Attachment(
+            AttachmentDataBuilder::default()
+                .link_mode(LinkMode::LinkedUrl)
+                .content_type("pdf")
+                .build()
+                .unwrap(),
+        );
+
+        assert_eq!(
+            attachment.validate(),
+            vec![
+                Diagnostic::error(
+                    "contentType",
+                    DiagnosticKind::InvalidContentType("pdf".into())
+                ),
+                Diagnostic::error("url", DiagnosticKind::MissingField),
+            ]
+        );
+
+        let attachment = ItemType::Attachment(
+            AttachmentDataBuilder::default()
+                .link_mode(LinkMode::ImportedFile)
+                .content_type("application/pdf")
+                .build()
+                .unwrap(),
+        );
+        assert!(attachment.is_valid());
+
+        let attachment = ItemType::Attachment(
+            AttachmentData::embedded_image("ABCD2345", "figure.pdf")
+                .build()
+                .unwrap(),
+        );
+        assert_eq!(
+            attachment.validate(),
+            vec![Diagnostic::error(
+                "contentType",
+                DiagnosticKind::InvalidContentType("application/pdf".into())
+            )]
+        );
+    }
+}
@@ -4,6 +4,8 @@ use serde::Serialize;
 
 pub mod collection;
 pub mod item;
+pub mod note;
+pub mod relations;
 pub mod shared_fields;
 
 /// Transform a data structure to a json object
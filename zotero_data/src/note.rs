@@ -0,0 +1,875 @@
+//! HTML of Zotero notes.
+//!
+//! The content of a note is HTML restricted to what the Zotero note editor understands. This module derives the
+//! title of a note the way Zotero does, converts notes to Markdown or plain text, and converts Markdown back to
+//! note HTML.
+//!
+//! ```rust
+//! use zotero_data::note::{html_to_markdown, markdown_to_html, note_title};
+//!
+//! let html = markdown_to_html("# Reading notes\n\nThe *first* chapter.");
+//! assert_eq!(html, "<h1>Reading notes</h1>\n<p>The <em>first</em> chapter.</p>\n");
+//! assert_eq!(note_title(&html), "Reading notes");
+//! assert_eq!(html_to_markdown(&html), "# Reading notes\n\nThe *first* chapter.");
+//! ```
+
+use pulldown_cmark::{html, Options, Parser};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// Zotero truncates note titles to this many characters.
+pub const MAX_TITLE_LENGTH: usize = 120;
+
+/// Elements kept by [`sanitize_html`], with their allowed attributes.
+const ALLOWED_ELEMENTS: [(&str, &[&str]); 35] = [
+    ("div", &["data-schema-version", "data-citation-items"]),
+    ("p", &["style"]),
+    ("h1", &["style"]),
+    ("h2", &["style"]),
+    ("h3", &["style"]),
+    ("h4", &["style"]),
+    ("h5", &["style"]),
+    ("h6", &["style"]),
+    ("pre", &[]),
+    ("blockquote", &[]),
+    ("ul", &[]),
+    ("ol", &["start"]),
+    ("li", &[]),
+    ("table", &[]),
+    ("thead", &[]),
+    ("tbody", &[]),
+    ("tr", &[]),
+    ("th", &["colspan", "rowspan"]),
+    ("td", &["colspan", "rowspan"]),
+    ("hr", &[]),
+    ("br", &[]),
+    (
+        "img",
+        &[
+            "src",
+            "alt",
+            "width",
+            "height",
+            "data-attachment-key",
+            "data-annotation",
+        ],
+    ),
+    ("a", &["href", "title"]),
+    (
+        "span",
+        &["class", "style", "data-citation", "data-annotation"],
+    ),
+    ("strong", &[]),
+    ("b", &[]),
+    ("em", &[]),
+    ("i", &[]),
+    ("u", &[]),
+    ("s", &[]),
+    ("strike", &[]),
+    ("del", &[]),
+    ("sub", &[]),
+    ("sup", &[]),
+    ("code", &[]),
+];
+
+/// CSS properties kept in `style` attributes.
+const ALLOWED_STYLES: [&str; 4] = ["color", "background-color", "text-align", "padding-left"];
+
+/// Elements removed along with their content.
+const DROPPED_ELEMENTS: [&str; 10] = [
+    "script", "style", "iframe", "object", "embed", "head", "title", "template", "svg", "math",
+];
+
+const VOID_ELEMENTS: [&str; 4] = ["br", "hr", "img", "meta"];
+
+const BLOCK_ELEMENTS: [&str; 17] = [
+    "p",
+    "div",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "pre",
+    "blockquote",
+    "ul",
+    "ol",
+    "li",
+    "table",
+    "tr",
+    "hr",
+    "body",
+];
+
+/// The title Zotero gives to a note: the first line of its text, at most [`MAX_TITLE_LENGTH`] characters long.
+pub fn note_title(html: &str) -> String {
+    let text = render(html, Format::Title);
+    let first_line = text.lines().map(str::trim).find(|line| !line.is_empty());
+    first_line
+        .unwrap_or_default()
+        .chars()
+        .take(MAX_TITLE_LENGTH)
+        .collect()
+}
+
+/// The text of a note, with a blank line between paragraphs.
+pub fn html_to_text(html: &str) -> String {
+    render(html, Format::Text)
+}
+
+/// Convert the HTML of a note to CommonMark. Elements without a Markdown equivalent are reduced to their text.
+pub fn html_to_markdown(html: &str) -> String {
+    render(html, Format::Markdown)
+}
+
+/// Convert CommonMark, with tables and strikethrough, to note HTML. Raw HTML in the Markdown is sanitized.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let parser = Parser::new_ext(
+        markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH,
+    );
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    sanitize_html(&output)
+}
+
+/// Keep only the elements and attributes accepted by the Zotero note editor. Scripts, styles and embedded
+/// content are removed with their content, other unknown elements are replaced by their content. Links must
+/// use `http`, `https`, `mailto` or `zotero` URLs, and images must be attachments or `data:image` URLs.
+pub fn sanitize_html(html: &str) -> String {
+    let mut output = String::new();
+    let mut dropped = 0;
+    for token in tokenize(html) {
+        match token {
+            Token::Start { name, .. } if DROPPED_ELEMENTS.contains(&name.as_str()) => dropped += 1,
+            Token::End(name) if DROPPED_ELEMENTS.contains(&name.as_str()) => {
+                dropped -= usize::from(dropped > 0)
+            }
+            _ if dropped > 0 => {}
+            Token::Text(text) => output.push_str(&escape(&text, false)),
+            Token::Start { name, attributes } => {
+                let Some((_, allowed)) = ALLOWED_ELEMENTS.iter().find(|(n, _)| *n == name) else {
+                    continue;
+                };
+                output.push('<');
+                output.push_str(&name);
+                for (attribute, value) in attributes {
+                    if let Some(value) = allowed_attribute(&name, allowed, &attribute, &value) {
+                        output.push_str(&format!(" {}=\"{}\"", attribute, escape(&value, true)));
+                    }
+                }
+                output.push('>');
+            }
+            Token::End(name) => {
+                if !VOID_ELEMENTS.contains(&name.as_str())
+                    && ALLOWED_ELEMENTS.iter().any(|(n, _)| *n == name)
+                {
+                    output.push_str(&format!("</{}>", name));
+                }
+            }
+        }
+    }
+    output
+}
+
+fn allowed_attribute(
+    element: &str,
+    allowed: &[&str],
+    attribute: &str,
+    value: &str,
+) -> Option<String> {
+    if !allowed.contains(&attribute) {
+        return None;
+    }
+    match attribute {
+        "href" => {
+            let scheme = value.split(':').next().unwrap_or_default().to_lowercase();
+            match !value.contains(':')
+                || ["http", "https", "mailto", "zotero"].contains(&scheme.as_str())
+            {
+                true => Some(value.to_string()),
+                false => None,
+            }
+        }
+        "src" if element == "img" => match value.starts_with("data:image/") {
+            true => Some(value.to_string()),
+            false => None,
+        },
+        "style" => {
+            let style = value
+                .split(';')
+                .filter(|declaration| {
+                    let property = declaration.split(':').next().unwrap_or_default().trim();
+                    ALLOWED_STYLES.contains(&property.to_lowercase().as_str())
+                        && !declaration.to_lowercase().contains("url(")
+                })
+                .map(str::trim)
+                .collect::<Vec<&str>>()
+                .join("; ");
+            match style.is_empty() {
+                true => None,
+                false => Some(style),
+            }
+        }
+        _ => Some(value.to_string()),
+    }
+}
+
+fn escape(text: &str, attribute: bool) -> String {
+    let text = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    match attribute {
+        true => text.replace('"', "&quot;"),
+        false => text,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    End(String),
+    Text(String),
+}
+
+/// A lenient HTML tokenizer, enough for the HTML of notes. Comments and doctypes are skipped, and the content
+/// of `script` and `style` elements is never returned.
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(position) = rest.find('<') {
+        text.push_str(&rest[..position]);
+        rest = &rest[position..];
+
+        let skip_to = |rest: &str, end: &str| match rest.find(end) {
+            Some(i) => i + end.len(),
+            None => rest.len(),
+        };
+        let next = rest[1..].chars().next();
+        if rest.starts_with("<!--") {
+            rest = &rest[skip_to(rest, "-->")..];
+            continue;
+        } else if matches!(next, Some('!' | '?')) {
+            rest = &rest[skip_to(rest, ">")..];
+            continue;
+        } else if !matches!(next, Some(c) if c.is_ascii_alphabetic() || c == '/') {
+            text.push('<');
+            rest = &rest[1..];
+            continue;
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token::Text(decode_entities(&text)));
+            text.clear();
+        }
+        let (token, length) = parse_tag(rest);
+        rest = &rest[length..];
+        if let Token::Start { name, .. } = &token {
+            if ["script", "style"].contains(&name.as_str()) {
+                let end = format!("</{}", name);
+                let content = find_ignore_ascii_case(rest, &end).unwrap_or(rest.len());
+                rest = &rest[content..];
+            }
+        }
+        tokens.push(token);
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        tokens.push(Token::Text(decode_entities(&text)));
+    }
+    tokens
+}
+
+/// The position of the ASCII `needle` in `haystack`, ignoring ASCII case. Lowercasing the whole haystack would
+/// change the length of some characters, and the offsets found in it wouldn't match `haystack`.
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Parse the tag at the start of `tag`, returning the token and the length of the tag.
+fn parse_tag(tag: &str) -> (Token, usize) {
+    let closing = tag.starts_with("</");
+    let start = if closing { 2 } else { 1 };
+    let mut chars = tag[start..].char_indices().peekable();
+
+    fn read_while(chars: &mut Peekable<CharIndices>, keep: impl Fn(char) -> bool) -> String {
+        let mut value = String::new();
+        while let Some((_, c)) = chars.next_if(|(_, c)| keep(*c)) {
+            value.push(c);
+        }
+        value
+    }
+
+    let name = read_while(&mut chars, |c| c.is_ascii_alphanumeric() || c == '-').to_lowercase();
+    let mut attributes = Vec::new();
+    loop {
+        read_while(&mut chars, |c| c.is_whitespace() || c == '/');
+        match chars.peek() {
+            None => break,
+            Some((i, '>')) => {
+                let length = start + i + 1;
+                return match closing {
+                    true => (Token::End(name), length),
+                    false => (Token::Start { name, attributes }, length),
+                };
+            }
+            Some(_) => {}
+        }
+        let attribute = read_while(&mut chars, |c| {
+            !c.is_whitespace() && !['=', '>', '/'].contains(&c)
+        })
+        .to_lowercase();
+        read_while(&mut chars, char::is_whitespace);
+        let mut value = String::new();
+        if chars.next_if(|(_, c)| *c == '=').is_some() {
+            read_while(&mut chars, char::is_whitespace);
+            value = match chars.next_if(|(_, c)| *c == '"' || *c == '\'') {
+                Some((_, quote)) => {
+                    let value = read_while(&mut chars, move |c| c != quote);
+                    chars.next();
+                    value
+                }
+                None => read_while(&mut chars, |c| !c.is_whitespace() && c != '>'),
+            };
+        }
+        if !attribute.is_empty() {
+            attributes.push((attribute, decode_entities(&value)));
+        }
+    }
+    match closing {
+        true => (Token::End(name), tag.len()),
+        false => (Token::Start { name, attributes }, tag.len()),
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(position) = rest.find('&') {
+        output.push_str(&rest[..position]);
+        rest = &rest[position..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let decoded = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => match entity.strip_prefix('#') {
+                Some(number) => match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => number.parse().ok(),
+                }
+                .and_then(char::from_u32),
+                None => None,
+            },
+        });
+        match (entity, decoded) {
+            (Some(entity), Some(c)) => {
+                output.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    /// Text where line breaks are spaces, like Zotero does to compute titles.
+    Title,
+    Text,
+    Markdown,
+}
+
+enum Container {
+    Quote,
+    List { ordered: bool, next: usize },
+}
+
+/// Renders HTML tokens as text or Markdown, line by line.
+struct Renderer {
+    format: Format,
+    output: String,
+    containers: Vec<Container>,
+    /// Line breaks to write before the next text, 2 for a new paragraph.
+    pending_breaks: usize,
+    /// The marker of a list item, written before its first line.
+    pending_marker: Option<String>,
+    /// Width of the markers of the list items being rendered, to indent their next lines.
+    indents: Vec<usize>,
+    links: Vec<Option<String>>,
+    pre: bool,
+    /// The text of the inline code being rendered, written unescaped within its fence at the end of the element.
+    code: Option<String>,
+    /// Whether something besides the prefix has been written on the current line.
+    line_has_content: bool,
+}
+
+impl Renderer {
+    fn block(&mut self, breaks: usize) {
+        if self.line_has_content {
+            self.pending_breaks = self.pending_breaks.max(breaks);
+        }
+    }
+
+    fn prefix(&self) -> String {
+        let mut prefix = String::new();
+        let mut indents = self.indents.iter();
+        for container in &self.containers {
+            match container {
+                Container::Quote => prefix.push_str("> "),
+                Container::List { .. } => {
+                    if let Some(indent) = indents.next() {
+                        prefix.push_str(&" ".repeat(*indent));
+                    }
+                }
+            }
+        }
+        prefix
+    }
+
+    fn flush(&mut self) {
+        if self.pending_breaks > 0 {
+            if !self.pre {
+                while self.output.ends_with(' ') {
+                    self.output.pop();
+                }
+            }
+            let prefix = match self.format {
+                Format::Markdown => self.prefix(),
+                _ => String::new(),
+            };
+            for i in 0..self.pending_breaks {
+                self.output.push('\n');
+                if i + 1 < self.pending_breaks {
+                    self.output.push_str(prefix.trim_end());
+                }
+            }
+            self.output.push_str(&prefix);
+            self.pending_breaks = 0;
+            self.line_has_content = false;
+        }
+        if let Some(marker) = self.pending_marker.take() {
+            self.output.push_str(&marker);
+        }
+    }
+
+    /// Write text as is, continuing the prefix of the current line on new lines.
+    fn raw(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.pending_breaks = 1;
+            }
+            if !line.is_empty() || i > 0 {
+                self.flush();
+            }
+            self.output.push_str(line);
+            self.line_has_content |= !line.is_empty();
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.pre {
+            self.raw(text);
+            return;
+        }
+        if let Some(code) = &mut self.code {
+            code.push_str(&text.replace('\n', " "));
+            return;
+        }
+        let at_line_start =
+            self.pending_breaks > 0 || !self.line_has_content || self.pending_marker.is_some();
+        let mut collapsed = String::new();
+        for c in text.chars() {
+            match c.is_whitespace() && c != '\u{a0}' {
+                true if collapsed.ends_with(' ')
+                    || (collapsed.is_empty() && (at_line_start || self.output.ends_with(' '))) => {}
+                true => collapsed.push(' '),
+                false => collapsed.push(c),
+            }
+        }
+        if collapsed.is_empty() {
+            return;
+        }
+        let collapsed = match self.format {
+            Format::Markdown if at_line_start => escape_line_start(&escape_markdown(&collapsed)),
+            Format::Markdown => escape_markdown(&collapsed),
+            _ => collapsed,
+        };
+        self.flush();
+        self.output.push_str(&collapsed);
+        self.line_has_content = true;
+    }
+
+    /// Write Markdown syntax, ignored in text formats.
+    fn markup(&mut self, markup: &str) {
+        if self.format == Format::Markdown && !self.pre {
+            self.flush();
+            self.output.push_str(markup);
+            self.line_has_content = true;
+        }
+    }
+
+    fn start(&mut self, name: &str, attributes: &[(String, String)]) {
+        let attribute = |attribute: &str| {
+            attributes
+                .iter()
+                .find(|(a, _)| a == attribute)
+                .map(|(_, value)| value.clone())
+        };
+        if BLOCK_ELEMENTS.contains(&name) && name != "li" {
+            self.block(match name {
+                // a nested list follows the line of its parent item
+                "ul" | "ol" if !self.indents.is_empty() => 1,
+                _ => 2,
+            });
+        }
+        match name {
+            "br" => match self.format {
+                Format::Title => self.text(" "),
+                Format::Text => self.block(1),
+                Format::Markdown => {
+                    self.markup("\\");
+                    self.block(1);
+                }
+            },
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse().unwrap_or(1);
+                self.markup(&format!("{} ", "#".repeat(level)));
+            }
+            "hr" => {
+                self.markup("---");
+                self.block(2);
+            }
+            "blockquote" => {
+                self.flush();
+                self.containers.push(Container::Quote);
+                if self.format == Format::Markdown && !self.line_has_content {
+                    self.output.push_str("> ");
+                }
+            }
+            "ul" | "ol" => self.containers.push(Container::List {
+                ordered: name == "ol",
+                next: attribute("start")
+                    .and_then(|start| start.parse().ok())
+                    .unwrap_or(1),
+            }),
+            "li" => {
+                self.block(1);
+                let marker = match self.containers.iter_mut().rev().find_map(|c| match c {
+                    Container::List { ordered, next } => Some((ordered, next)),
+                    Container::Quote => None,
+                }) {
+                    Some((true, next)) => {
+                        *next += 1;
+                        format!("{}. ", *next - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                // the next lines of the item are indented by the width of its marker
+                self.flush();
+                self.indents.push(marker.len());
+                if self.format == Format::Markdown {
+                    self.pending_marker = Some(marker);
+                }
+            }
+            "pre" => {
+                self.markup("```");
+                self.block(1);
+                self.pre = true;
+            }
+            "strong" | "b" => self.markup("**"),
+            "em" | "i" => self.markup("*"),
+            "s" | "strike" | "del" => self.markup("~~"),
+            "code" if self.format == Format::Markdown && !self.pre => {
+                self.code = Some(String::new())
+            }
+            "a" => {
+                let href = attribute("href");
+                if href.is_some() {
+                    self.markup("[");
+                }
+                self.links.push(href);
+            }
+            "img" => {
+                if let Some(src) = attribute("src") {
+                    let alt = attribute("alt").unwrap_or_default();
+                    self.markup(&format!("![{}]({})", escape_markdown(&alt), src));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &str) {
+        match name {
+            "blockquote" | "ul" | "ol" => {
+                self.containers.pop();
+            }
+            "li" => {
+                self.pending_marker = None;
+                self.indents.pop();
+            }
+            "pre" => {
+                self.pre = false;
+                while self.output.ends_with('\n') {
+                    self.output.pop();
+                }
+                self.pending_breaks = 1;
+                self.markup("```");
+            }
+            "strong" | "b" => self.markup("**"),
+            "em" | "i" => self.markup("*"),
+            "s" | "strike" | "del" => self.markup("~~"),
+            "code" => {
+                if let Some(code) = self.code.take().filter(|code| !code.is_empty()) {
+                    // the fence is longer than any run of backticks in the code
+                    let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+                    let fence = "`".repeat(longest + 1);
+                    let padding = match code.starts_with('`') || code.ends_with('`') {
+                        true => " ",
+                        false => "",
+                    };
+                    self.markup(&format!("{0}{1}{2}{1}{0}", fence, padding, code));
+                }
+            }
+            "a" => {
+                if let Some(Some(href)) = self.links.pop() {
+                    self.markup(&format!("]({})", href));
+                }
+            }
+            "td" | "th" => self.text(" "),
+            _ => {}
+        }
+        if BLOCK_ELEMENTS.contains(&name) {
+            self.block(match name {
+                "ul" | "ol" if !self.indents.is_empty() => 1,
+                "li" => 1,
+                _ => 2,
+            });
+        }
+    }
+}
+
+fn render(html: &str, format: Format) -> String {
+    let mut renderer = Renderer {
+        format,
+        output: String::new(),
+        containers: Vec::new(),
+        pending_breaks: 0,
+        pending_marker: None,
+        indents: Vec::new(),
+        links: Vec::new(),
+        pre: false,
+        code: None,
+        line_has_content: false,
+    };
+    let mut dropped = 0;
+    for token in tokenize(html) {
+        match token {
+            Token::Start { name, .. } if DROPPED_ELEMENTS.contains(&name.as_str()) => dropped += 1,
+            Token::End(name) if DROPPED_ELEMENTS.contains(&name.as_str()) => {
+                dropped -= usize::from(dropped > 0)
+            }
+            _ if dropped > 0 => {}
+            Token::Start { name, attributes } => {
+                renderer.start(&name, &attributes);
+                if VOID_ELEMENTS.contains(&name.as_str()) {
+                    renderer.end(&name);
+                }
+            }
+            Token::End(name) => renderer.end(&name),
+            Token::Text(text) => renderer.text(&text),
+        }
+    }
+    renderer.output.trim_end().to_string()
+}
+
+/// Escape the characters that would otherwise be read as Markdown syntax.
+pub(crate) fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if ['\\', '*', '_', '`', '[', ']', '<', '#'].contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape a block marker starting a line, e.g. `- ` or `1. `, so that the text isn't read as a list, a quote,
+/// a table or a heading underline.
+fn escape_line_start(text: &str) -> String {
+    if text.starts_with(['-', '+', '>', '|', '=']) {
+        return format!("\\{}", text);
+    }
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    match text[digits..].starts_with(['.', ')']) && digits > 0 {
+        true => format!("{}\\{}", &text[..digits], &text[digits..]),
+        false => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test_note {
+    use super::*;
+
+    #[test]
+    fn title() {
+        assert_eq!(
+            note_title(
+                "<div data-schema-version=\"9\"><h1>Chapter&nbsp;1 &amp; 2</h1><p>Lorem</p></div>"
+            ),
+            "Chapter\u{a0}1 & 2"
+        );
+        assert_eq!(
+            note_title("<p>First<br>line</p><p>Second</p>"),
+            "First line"
+        );
+        assert_eq!(note_title(&"a".repeat(200)).len(), MAX_TITLE_LENGTH);
+        assert_eq!(note_title(""), "");
+    }
+
+    #[test]
+    fn html_to_markdown_and_text() {
+        let html = r#"<div data-schema-version="9"><h2>Summary</h2>
+<p>A <strong>bold</strong> and <a href="https://www.zotero.org">linked</a> claim_with*stars*.</p>
+<ul><li>first</li><li>second<ol start="3"><li>nested</li></ol></li></ul>
+<blockquote><p>Quoted<br>text</p></blockquote>
+<pre><code>let x = 1;
+let y = 2;</code></pre><script>alert(1)</script></div>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## Summary\n\n\
+             A **bold** and [linked](https://www.zotero.org) claim\\_with\\*stars\\*.\n\n\
+             - first\n\
+             - second\n  \
+               3. nested\n\n\
+             > Quoted\\\n\
+             > text\n\n\
+             ```\nlet x = 1;\nlet y = 2;\n```"
+        );
+        assert_eq!(
+            html_to_text(html),
+            "Summary\n\nA bold and linked claim_with*stars*.\n\nfirst\nsecond\nnested\n\nQuoted\ntext\n\nlet x = 1;\nlet y = 2;"
+        );
+    }
+
+    #[test]
+    fn markdown_round_trip() {
+        let markdown = "# Notes\n\nSee [Zotero](https://www.zotero.org), **really**.\n\n- one\n- two\n\n> quoted";
+        let html = markdown_to_html(markdown);
+        assert_eq!(
+            html,
+            "<h1>Notes</h1>\n<p>See <a href=\"https://www.zotero.org\">Zotero</a>, <strong>really</strong>.</p>\n\
+             <ul>\n<li>one</li>\n<li>two</li>\n</ul>\n<blockquote>\n<p>quoted</p>\n</blockquote>\n"
+        );
+        assert_eq!(html_to_markdown(&html), markdown);
+
+        for markdown in [
+            "Use `snake_case` names.",
+            "Type ``a ` b`` or `` `quoted` ``.",
+        ] {
+            assert_eq!(html_to_markdown(&markdown_to_html(markdown)), markdown);
+        }
+    }
+
+    #[test]
+    fn block_markers_in_text() {
+        let markdown =
+            html_to_markdown("<p>- not a list</p><p>1. not a list either</p><p>> nor a quote</p>");
+        assert_eq!(
+            markdown,
+            "\\- not a list\n\n1\\. not a list either\n\n\\> nor a quote"
+        );
+        assert_eq!(
+            markdown_to_html(&markdown),
+            "<p>- not a list</p>\n<p>1. not a list either</p>\n<p>&gt; nor a quote</p>\n"
+        );
+        assert_eq!(
+            html_to_markdown("<p>Page 1. Lorem - ipsum</p>"),
+            "Page 1. Lorem - ipsum"
+        );
+    }
+
+    #[test]
+    fn sanitization() {
+        assert_eq!(
+            sanitize_html(
+                r#"<p onclick="x()" style="color: red; position: fixed">Hi<script>alert("<p>")</script></p><iframe src="x"><p>hidden</p></iframe><custom>kept</custom><a href="javascript:alert(1)">link</a><img src="https://example.org/x.png" data-attachment-key="ABCD2345">"#
+            ),
+            r#"<p style="color: red">Hi</p>kept<a>link</a><img data-attachment-key="ABCD2345">"#
+        );
+        assert_eq!(
+            sanitize_html("a < b &amp; <b title='x'>c</b>"),
+            "a &lt; b &amp; <b>c</b>"
+        );
+        assert_eq!(
+            markdown_to_html("<span onmouseover=\"x()\">raw</span>"),
+            "<p><span>raw</span></p>\n"
+        );
+        // characters whose lowercase form has another length
+        assert_eq!(
+            sanitize_html("<script>\u{212A}</script><p>ok</p>"),
+            "<p>ok</p>"
+        );
+        assert_eq!(
+            sanitize_html("<script>İ</SCRIPT><p>kept</p>"),
+            "<p>kept</p>"
+        );
+    }
+
+    #[test]
+    fn note_data() {
+        let note: crate::item::NoteData = serde_json::from_value(serde_json::json!({
+            "key": "NNNNNNNN",
+            "version": 3,
+            "itemType": "note",
+            "note": "<div data-schema-version=\"9\"><p>Reading notes</p><p>Lorem</p></div>",
+            "tags": [],
+            "relations": {},
+            "dateAdded": "2020-01-01T00:00:00Z",
+            "dateModified": "2020-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(note.title, "Reading notes");
+        assert_eq!(note.text(), "Reading notes\n\nLorem");
+        assert!(serde_json::to_value(&note).unwrap().get("title").is_none());
+
+        let mut note = crate::item::NoteData::from_markdown("**Summary**\n\nLorem");
+        assert_eq!(note.title, "Summary");
+        assert_eq!(note.markdown(), "**Summary**\n\nLorem");
+        note.set_note("<p>Other</p>");
+        assert_eq!(note.title, "Other");
+
+        let note = crate::item::NoteDataBuilder::default()
+            .note("<h1>Built</h1>")
+            .build()
+            .unwrap();
+        assert_eq!(note.title, "Built");
+    }
+}
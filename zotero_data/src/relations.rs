@@ -0,0 +1,341 @@
+//! Relations between Zotero items and collections.
+//!
+//! Zotero stores relations as an object mapping a predicate to one URI or to an array of URIs:
+//!
+//! ```json
+//! {
+//!     "dc:relation": ["http://zotero.org/users/123456/items/ABCD2345", "http://zotero.org/groups/98765/items/EFGH6789"],
+//!     "owl:sameAs": "http://zotero.org/groups/98765/items/IJKL0123"
+//! }
+//! ```
+//!
+//! ```rust
+//! use zotero_data::relations::{ItemUri, LibraryKind, RelationPredicate, Relations};
+//!
+//! let mut relations = Relations::default();
+//! relations.add(RelationPredicate::Relation, "http://zotero.org/users/123456/items/ABCD2345");
+//!
+//! let uri: ItemUri = relations.related()[0].parse().unwrap();
+//! assert_eq!(uri.library_kind, LibraryKind::User);
+//! assert_eq!(uri.library_id, 123456);
+//! assert_eq!(uri.item_key, "ABCD2345");
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// The predicate of a relation. Predicates unknown to this crate are kept in [`RelationPredicate::Other`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RelationPredicate {
+    /// `dc:relation`, a related item as set in the "Related" pane of Zotero. Zotero stores it on both items.
+    Relation,
+    /// `owl:sameAs`, the same item in another library.
+    SameAs,
+    /// `dc:replaces`, an item merged into this one.
+    Replaces,
+    Other(String),
+}
+
+impl RelationPredicate {
+    /// The predicate as written in the `relations` object.
+    pub fn as_str(&self) -> &str {
+        match self {
+            RelationPredicate::Relation => "dc:relation",
+            RelationPredicate::SameAs => "owl:sameAs",
+            RelationPredicate::Replaces => "dc:replaces",
+            RelationPredicate::Other(s) => s,
+        }
+    }
+}
+
+impl fmt::Display for RelationPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for RelationPredicate {
+    fn from(s: &str) -> RelationPredicate {
+        match s {
+            "dc:relation" => RelationPredicate::Relation,
+            "owl:sameAs" => RelationPredicate::SameAs,
+            "dc:replaces" => RelationPredicate::Replaces,
+            other => RelationPredicate::Other(other.to_string()),
+        }
+    }
+}
+
+/// The kind of library an [`ItemUri`] points to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LibraryKind {
+    User,
+    Group,
+}
+
+impl LibraryKind {
+    /// Parse the `type` of a [`Library`](crate::shared_fields::Library), `user` or `group`.
+    pub fn from_library_type(library_type: &str) -> Option<LibraryKind> {
+        match library_type {
+            "user" => Some(LibraryKind::User),
+            "group" => Some(LibraryKind::Group),
+            _ => None,
+        }
+    }
+
+    /// The segment of the library in URLs: `users` or `groups`.
+    pub fn path_segment(&self) -> &'static str {
+        match self {
+            LibraryKind::User => "users",
+            LibraryKind::Group => "groups",
+        }
+    }
+}
+
+/// The URI Zotero uses to identify an item in relations, e.g. `http://zotero.org/users/123456/items/ABCD2345`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ItemUri {
+    pub library_kind: LibraryKind,
+    pub library_id: usize,
+    pub item_key: String,
+}
+
+impl ItemUri {
+    pub fn new<S: Into<String>>(
+        library_kind: LibraryKind,
+        library_id: usize,
+        item_key: S,
+    ) -> ItemUri {
+        ItemUri {
+            library_kind,
+            library_id,
+            item_key: item_key.into(),
+        }
+    }
+}
+
+impl fmt::Display for ItemUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "http://zotero.org/{}/{}/items/{}",
+            self.library_kind.path_segment(),
+            self.library_id,
+            self.item_key
+        )
+    }
+}
+
+impl FromStr for ItemUri {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("\"{}\" is not a Zotero item URI", s);
+        let path = s
+            .strip_prefix("http://zotero.org/")
+            .or_else(|| s.strip_prefix("https://zotero.org/"))
+            .ok_or_else(invalid)?;
+
+        match path.split('/').collect::<Vec<&str>>().as_slice() {
+            [library, id, "items", key] if !key.is_empty() => {
+                let library_kind = match *library {
+                    "users" => LibraryKind::User,
+                    "groups" => LibraryKind::Group,
+                    _ => return Err(invalid()),
+                };
+                let library_id = id.parse::<usize>().map_err(|_| invalid())?;
+                Ok(ItemUri::new(library_kind, library_id, *key))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// The `relations` of an item or a collection.
+///
+/// Zotero writes a predicate with a single URI as a string and a predicate with several URIs as an array;
+/// both forms are accepted and written back the same way.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Relations(BTreeMap<String, Vec<String>>);
+
+impl Relations {
+    /// URIs related to the item through `predicate`.
+    pub fn get(&self, predicate: &RelationPredicate) -> &[String] {
+        self.0
+            .get(predicate.as_str())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// URIs of the `dc:relation` predicate.
+    pub fn related(&self) -> &[String] {
+        self.get(&RelationPredicate::Relation)
+    }
+
+    /// URIs of the `owl:sameAs` predicate.
+    pub fn same_as(&self) -> &[String] {
+        self.get(&RelationPredicate::SameAs)
+    }
+
+    /// URIs of the `dc:replaces` predicate.
+    pub fn replaces(&self) -> &[String] {
+        self.get(&RelationPredicate::Replaces)
+    }
+
+    /// Items related through `predicate`. URIs that do not point to a Zotero item are skipped.
+    pub fn items(&self, predicate: &RelationPredicate) -> Vec<ItemUri> {
+        self.get(predicate)
+            .iter()
+            .filter_map(|uri| uri.parse().ok())
+            .collect()
+    }
+
+    /// Add a relation. Returns `false` if the relation already exists.
+    pub fn add<P: Into<RelationPredicate>, S: ToString>(&mut self, predicate: P, uri: S) -> bool {
+        let uri = uri.to_string();
+        let uris = self
+            .0
+            .entry(predicate.into().as_str().to_string())
+            .or_default();
+        match uris.contains(&uri) {
+            true => false,
+            false => {
+                uris.push(uri);
+                true
+            }
+        }
+    }
+
+    /// Remove a relation. Returns `false` if the relation did not exist.
+    pub fn remove<P: Into<RelationPredicate>>(&mut self, predicate: P, uri: &str) -> bool {
+        let predicate = predicate.into();
+        let Some(uris) = self.0.get_mut(predicate.as_str()) else {
+            return false;
+        };
+        let len = uris.len();
+        uris.retain(|u| u != uri);
+        let removed = uris.len() != len;
+        if uris.is_empty() {
+            self.0.remove(predicate.as_str());
+        }
+        removed
+    }
+
+    pub fn contains<P: Into<RelationPredicate>>(&self, predicate: P, uri: &str) -> bool {
+        self.get(&predicate.into()).iter().any(|u| u == uri)
+    }
+
+    /// All predicates with their URIs.
+    pub fn iter(&self) -> impl Iterator<Item = (RelationPredicate, &[String])> {
+        self.0
+            .iter()
+            .map(|(predicate, uris)| (RelationPredicate::from(predicate.as_str()), uris.as_slice()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl<'de> Deserialize<'de> for Relations {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let relations = BTreeMap::<String, OneOrMany>::deserialize(deserializer)?;
+        Ok(Relations(
+            relations
+                .into_iter()
+                .map(|(predicate, uris)| match uris {
+                    OneOrMany::One(uri) => (predicate, vec![uri]),
+                    OneOrMany::Many(uris) => (predicate, uris),
+                })
+                .collect(),
+        ))
+    }
+}
+
+impl Serialize for Relations {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (predicate, uris) in &self.0 {
+            match uris.as_slice() {
+                [uri] => map.serialize_entry(predicate, uri)?,
+                uris => map.serialize_entry(predicate, uris)?,
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod test_relations {
+    use super::*;
+
+    #[test]
+    fn string_and_array_values() {
+        let input = r#"{
+            "dc:relation": ["http://zotero.org/users/1/items/AAAAAAAA", "http://zotero.org/groups/2/items/BBBBBBBB"],
+            "owl:sameAs": "http://zotero.org/groups/2/items/CCCCCCCC"
+        }"#;
+        let relations = serde_json::from_str::<Relations>(input).unwrap();
+        assert_eq!(relations.related().len(), 2);
+        assert_eq!(
+            relations.same_as(),
+            ["http://zotero.org/groups/2/items/CCCCCCCC"]
+        );
+        assert!(relations.replaces().is_empty());
+
+        let output = serde_json::to_value(&relations).unwrap();
+        assert_eq!(
+            output,
+            serde_json::from_str::<serde_json::Value>(input).unwrap()
+        );
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let uri = ItemUri::new(LibraryKind::Group, 2, "BBBBBBBB");
+        let mut relations = Relations::default();
+        assert!(relations.add(RelationPredicate::Relation, &uri));
+        assert!(!relations.add("dc:relation", &uri));
+        assert!(relations.contains(RelationPredicate::Relation, &uri.to_string()));
+        assert_eq!(
+            relations.items(&RelationPredicate::Relation),
+            vec![uri.clone()]
+        );
+        assert_eq!(
+            serde_json::to_value(&relations).unwrap(),
+            serde_json::json!({"dc:relation": "http://zotero.org/groups/2/items/BBBBBBBB"})
+        );
+
+        assert!(relations.remove(RelationPredicate::Relation, &uri.to_string()));
+        assert!(!relations.remove(RelationPredicate::Relation, &uri.to_string()));
+        assert!(relations.is_empty());
+    }
+
+    #[test]
+    fn item_uri_parsing() {
+        let uri: ItemUri = "http://zotero.org/users/123456/items/ABCD2345"
+            .parse()
+            .unwrap();
+        assert_eq!(uri, ItemUri::new(LibraryKind::User, 123456, "ABCD2345"));
+        assert_eq!(
+            uri.to_string(),
+            "http://zotero.org/users/123456/items/ABCD2345"
+        );
+
+        assert!("http://zotero.org/groups/1/collections/ABCD2345"
+            .parse::<ItemUri>()
+            .is_err());
+        assert!("https://example.org/users/1/items/ABCD2345"
+            .parse::<ItemUri>()
+            .is_err());
+    }
+}
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::item::Creator;
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Library {
-    r#type: String,
+    pub r#type: String,
     pub id: usize,
     pub name: String,
     pub links: Links,
@@ -28,6 +31,36 @@ pub struct Tag {
     pub tag: String,
 }
 
+/// Fields unknown to this crate, kept as they were received so that they are written back unchanged
+/// when the data is sent to the Zotero API.
+pub type UnknownFields = serde_json::Map<String, serde_json::Value>;
+
+/// Serde helpers for the `deleted` flag of trashed items. Zotero sends it as `1` or `true`, and omits it for
+/// items that are not in the trash.
+pub(crate) mod deleted {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn is_false(deleted: &bool) -> bool {
+        !deleted
+    }
+
+    pub fn is_set(value: &serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::Bool(deleted) => *deleted,
+            serde_json::Value::Number(deleted) => deleted.as_f64() != Some(0.0),
+            _ => false,
+        }
+    }
+
+    pub fn serialize<S: Serializer>(deleted: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(u8::from(*deleted))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+        Ok(is_set(&serde_json::Value::deserialize(deserializer)?))
+    }
+}
+
 pub trait Identifier {
     fn key(&self) -> &String;
 }
@@ -44,6 +77,70 @@ pub trait ItemCommon {
     }
 }
 
+/// Error returned by [`ItemFields::set_field`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldError {
+    /// The item type has no field with this name.
+    UnknownField(String),
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::UnknownField(field) => write!(f, "unknown field `{}`", field),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+/// Accessors for the fields shared by most item types, whatever their name in a given item type.
+/// A case's `dateDecided` is its [`date`](ItemFields::date), a patent's `issueDate` as well.
+///
+/// Accessors return `None` when the item type has no such field, and `Some` with a possibly empty value otherwise.
+/// `#[derive(ItemFields)]` maps fields through their `#[zotero(base = "...")]` attribute, or by name.
+pub trait ItemFields {
+    /// Value of a field by its Zotero name, e.g. `publicationTitle`. Type-specific fields can also be
+    /// reached through their base field, e.g. `title` for the `caseName` of a case.
+    fn get_field(&self, name: &str) -> Option<&str>;
+
+    /// Set a field by its Zotero name. Fails if the item type has no such field.
+    fn set_field<S: Into<String>>(&mut self, name: &str, value: S) -> Result<(), FieldError>;
+
+    /// Iterate over the `(Zotero name, value)` pairs of every text field of the item, empty or not.
+    /// The key, the item type, creators, tags, collections and relations are not included.
+    fn fields(&self) -> std::vec::IntoIter<(&str, &str)>;
+
+    fn creators(&self) -> &[Creator];
+    fn creators_mut(&mut self) -> Option<&mut Vec<Creator>>;
+
+    /// Keys of the collections containing the item.
+    fn collections(&self) -> &[String];
+    fn collections_mut(&mut self) -> Option<&mut Vec<String>>;
+
+    fn date(&self) -> Option<&str>;
+    fn date_mut(&mut self) -> Option<&mut String>;
+
+    fn url(&self) -> Option<&str>;
+    fn url_mut(&mut self) -> Option<&mut String>;
+
+    fn doi(&self) -> Option<&str>;
+    fn doi_mut(&mut self) -> Option<&mut String>;
+
+    fn abstract_note(&self) -> Option<&str>;
+    fn abstract_note_mut(&mut self) -> Option<&mut String>;
+
+    fn language(&self) -> Option<&str>;
+    fn language_mut(&mut self) -> Option<&mut String>;
+
+    fn extra(&self) -> Option<&str>;
+    fn extra_mut(&mut self) -> Option<&mut String>;
+
+    /// Key of the parent of a child attachment or note.
+    fn parent_item(&self) -> Option<&str>;
+    fn parent_item_mut(&mut self) -> Option<&mut String>;
+}
+
 #[cfg(test)]
 mod test_shared_fields_deserialization {
     use super::*;
@@ -3,9 +3,10 @@ extern crate quote;
 extern crate syn;
 
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
 
-#[proc_macro_derive(ItemCommon)]
+#[proc_macro_derive(ItemCommon, attributes(zotero))]
 pub fn tagable_macro_derive(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
 
@@ -68,11 +69,24 @@ fn impl_tagable_macro(ast: &syn::DeriveInput) -> TokenStream {
                 }
             }
         }
-        syn::Data::Struct(_d) => {
+        syn::Data::Struct(d) => {
+            // the title is either a `title` field or a type-specific field mapped to it
+            let title = d
+                .fields
+                .iter()
+                .find(|f| base_field(f).as_deref() == Some("title"))
+                .or_else(|| {
+                    d.fields
+                        .iter()
+                        .find(|f| f.ident.as_ref().is_some_and(|i| i == "title"))
+                })
+                .and_then(|f| f.ident.as_ref())
+                .expect("#[derive(ItemCommon)] requires a title field");
+
             quote! {
                 impl ItemCommon for #name {
                     fn title(&self) -> &str {
-                        &self.title
+                        &self.#title
                     }
 
                     fn key(&self) -> &str {
@@ -91,3 +105,327 @@ fn impl_tagable_macro(ast: &syn::DeriveInput) -> TokenStream {
     };
     gen.into()
 }
+
+/// String accessors of `ItemFields`: method name, Zotero (base) field name and default Rust field name.
+const STRING_FIELDS: [(&str, &str, &str); 7] = [
+    ("date", "date", "date"),
+    ("url", "url", "url"),
+    ("doi", "DOI", "doi"),
+    ("abstract_note", "abstractNote", "abstract_note"),
+    ("language", "language", "language"),
+    ("extra", "extra", "extra"),
+    ("parent_item", "parentItem", "parent_item"),
+];
+
+#[proc_macro_derive(ItemFields, attributes(zotero))]
+pub fn item_fields_macro_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    impl_item_fields_macro(&ast)
+}
+
+fn impl_item_fields_macro(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+
+    let gen = match &ast.data {
+        syn::Data::Enum(d) => {
+            // every variant holds a type implementing ItemFields, accessors are forwarded to it
+            let forward = |method: &syn::Ident| {
+                let arms = d.variants.iter().map(|v| {
+                    let vid = &v.ident;
+                    quote! { #name::#vid(x) => x.#method() }
+                });
+                quote! {
+                    match self {
+                        #(#arms),*
+                    }
+                }
+            };
+
+            let mut accessors = Vec::new();
+            for (method, _, _) in STRING_FIELDS {
+                let getter = format_ident!("{}", method);
+                let setter = format_ident!("{}_mut", method);
+                let get = forward(&getter);
+                let set = forward(&setter);
+                accessors.push(quote! {
+                    fn #getter(&self) -> Option<&str> {
+                        #get
+                    }
+
+                    fn #setter(&mut self) -> Option<&mut String> {
+                        #set
+                    }
+                });
+            }
+
+            let creators = forward(&format_ident!("creators"));
+            let creators_mut = forward(&format_ident!("creators_mut"));
+            let collections = forward(&format_ident!("collections"));
+            let collections_mut = forward(&format_ident!("collections_mut"));
+
+            let get_field_arms = d.variants.iter().map(|v| {
+                let vid = &v.ident;
+                quote! { #name::#vid(x) => x.get_field(name) }
+            });
+            let set_field_arms = d.variants.iter().map(|v| {
+                let vid = &v.ident;
+                quote! { #name::#vid(x) => x.set_field(name, value) }
+            });
+            let fields = forward(&format_ident!("fields"));
+
+            quote! {
+                impl ItemFields for #name {
+                    fn get_field(&self, name: &str) -> Option<&str> {
+                        match self {
+                            #(#get_field_arms),*
+                        }
+                    }
+
+                    fn set_field<S: Into<String>>(&mut self, name: &str, value: S) -> Result<(), FieldError> {
+                        match self {
+                            #(#set_field_arms),*
+                        }
+                    }
+
+                    fn fields(&self) -> std::vec::IntoIter<(&str, &str)> {
+                        #fields
+                    }
+
+                    fn creators(&self) -> &[Creator] {
+                        #creators
+                    }
+
+                    fn creators_mut(&mut self) -> Option<&mut Vec<Creator>> {
+                        #creators_mut
+                    }
+
+                    fn collections(&self) -> &[String] {
+                        #collections
+                    }
+
+                    fn collections_mut(&mut self) -> Option<&mut Vec<String>> {
+                        #collections_mut
+                    }
+
+                    #(#accessors)*
+                }
+            }
+        }
+        syn::Data::Struct(d) => {
+            let field_named = |name: &str| {
+                d.fields
+                    .iter()
+                    .find(|f| f.ident.as_ref().is_some_and(|i| i == name))
+                    .and_then(|f| f.ident.as_ref())
+            };
+
+            let mut accessors = Vec::new();
+            for (method, base, default_field) in STRING_FIELDS {
+                let getter = format_ident!("{}", method);
+                let setter = format_ident!("{}_mut", method);
+                // a type-specific field mapped to the base field wins over a field with the default name
+                let field = d
+                    .fields
+                    .iter()
+                    .find(|f| base_field(f).as_deref() == Some(base))
+                    .and_then(|f| f.ident.as_ref())
+                    .or_else(|| field_named(default_field));
+                accessors.push(match field {
+                    Some(field) => quote! {
+                        fn #getter(&self) -> Option<&str> {
+                            Some(&self.#field)
+                        }
+
+                        fn #setter(&mut self) -> Option<&mut String> {
+                            Some(&mut self.#field)
+                        }
+                    },
+                    None => quote! {
+                        fn #getter(&self) -> Option<&str> {
+                            None
+                        }
+
+                        fn #setter(&mut self) -> Option<&mut String> {
+                            None
+                        }
+                    },
+                });
+            }
+
+            let creators = match field_named("creators") {
+                Some(field) => quote! {
+                    fn creators(&self) -> &[Creator] {
+                        &self.#field
+                    }
+
+                    fn creators_mut(&mut self) -> Option<&mut Vec<Creator>> {
+                        Some(&mut self.#field)
+                    }
+                },
+                None => quote! {
+                    fn creators(&self) -> &[Creator] {
+                        &[]
+                    }
+
+                    fn creators_mut(&mut self) -> Option<&mut Vec<Creator>> {
+                        None
+                    }
+                },
+            };
+
+            let collections = match field_named("collections") {
+                Some(field) => quote! {
+                    fn collections(&self) -> &[String] {
+                        self.#field.as_deref().unwrap_or_default()
+                    }
+
+                    fn collections_mut(&mut self) -> Option<&mut Vec<String>> {
+                        Some(self.#field.get_or_insert_with(Vec::new))
+                    }
+                },
+                None => quote! {
+                    fn collections(&self) -> &[String] {
+                        &[]
+                    }
+
+                    fn collections_mut(&mut self) -> Option<&mut Vec<String>> {
+                        None
+                    }
+                },
+            };
+
+            // Zotero fields are the serialized `String` fields, except the key and the item type
+            let mut zotero_fields = Vec::new();
+            for field in &d.fields {
+                let Some(ident) = field.ident.as_ref() else {
+                    continue;
+                };
+                let serde = serde_field(field);
+                let rust_name = ident.unraw().to_string();
+                if serde.skip_serializing
+                    || rust_name == "key"
+                    || rust_name == "item_type"
+                    || !is_string(&field.ty)
+                {
+                    continue;
+                }
+                let zotero_name = serde.rename.unwrap_or_else(|| camel_case(&rust_name));
+                zotero_fields.push((ident, zotero_name, base_field(field)));
+            }
+
+            let names: Vec<&String> = zotero_fields.iter().map(|(_, n, _)| n).collect();
+            // a type-specific field can also be reached through its base field, e.g. `title` for `caseName`
+            let patterns: Vec<_> = zotero_fields
+                .iter()
+                .map(|(_, zotero_name, base)| match base {
+                    Some(base) if !names.contains(&base) => quote! { #zotero_name | #base },
+                    _ => quote! { #zotero_name },
+                })
+                .collect();
+            let idents: Vec<_> = zotero_fields.iter().map(|(i, _, _)| *i).collect();
+
+            quote! {
+                impl ItemFields for #name {
+                    fn get_field(&self, name: &str) -> Option<&str> {
+                        match name {
+                            #(#patterns => Some(&self.#idents),)*
+                            _ => None,
+                        }
+                    }
+
+                    fn set_field<S: Into<String>>(&mut self, name: &str, value: S) -> Result<(), FieldError> {
+                        match name {
+                            #(#patterns => self.#idents = value.into(),)*
+                            _ => return Err(FieldError::UnknownField(name.to_string())),
+                        }
+                        Ok(())
+                    }
+
+                    fn fields(&self) -> std::vec::IntoIter<(&str, &str)> {
+                        vec![#((#names, self.#idents.as_str())),*].into_iter()
+                    }
+
+                    #creators
+
+                    #collections
+
+                    #(#accessors)*
+                }
+            }
+        }
+        _ => {
+            panic!("#[derive(ItemFields)] is only available for Enum and Struct Types!")
+        }
+    };
+    gen.into()
+}
+
+#[derive(Default)]
+struct SerdeField {
+    rename: Option<String>,
+    skip_serializing: bool,
+}
+
+/// Read the `rename` and `skip_serializing` options of the `#[serde]` attributes of a struct field
+fn serde_field(field: &syn::Field) -> SerdeField {
+    let mut serde = SerdeField::default();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                serde.rename = Some(value.value());
+            } else if meta.path.is_ident("skip_serializing") || meta.path.is_ident("skip") {
+                serde.skip_serializing = true;
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| {
+                    if nested.input.peek(syn::Token![=]) {
+                        nested.value()?.parse::<syn::Expr>()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })
+        .expect("invalid #[serde] attribute");
+    }
+    serde
+}
+
+fn is_string(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(p) if p.qself.is_none() && p.path.is_ident("String"))
+}
+
+fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Read the base field of a struct field from its `#[zotero(base = "...")]` attribute
+fn base_field(field: &syn::Field) -> Option<String> {
+    let mut base = None;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("zotero")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("base") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                base = Some(value.value());
+            }
+            Ok(())
+        })
+        .expect("invalid #[zotero] attribute");
+    }
+    base
+}